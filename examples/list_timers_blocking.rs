@@ -0,0 +1,23 @@
+use systemd_client::{boot_time, manager, models::Unit, timer, Result};
+
+fn main() -> Result<()> {
+    let boot_time = boot_time()?;
+    let client = manager::build_blocking_proxy()?;
+    let units = client.list_units()?;
+    for unit in units {
+        let unit: Unit = unit.into();
+        if !unit.name.ends_with(".timer") {
+            continue;
+        }
+        let client = timer::build_blocking_proxy(unit.object_path)?;
+        let timer_props = client.get_properties()?;
+        println!(
+            "{} next: {:?} last: {:?} activates: {}",
+            unit.name,
+            timer_props.next_elapse(boot_time),
+            timer_props.last_trigger,
+            timer_props.unit
+        );
+    }
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use systemd_client::{
     create_unit_configuration_file, manager, unit, Result, ServiceConfiguration,
-    ServiceUnitConfiguration, UnitActiveStateType, UnitConfiguration, UnitLoadStateType, UnitProps,
+    ServiceUnitConfiguration, UnitActiveStateType, UnitConfiguration, UnitLoadStateType, UnitName,
     UnitSubStateType,
 };
 
@@ -19,17 +19,17 @@ fn main() -> Result<()> {
         .service(svc_builder)
         .build();
     let svc_unit_literal = format!("{}", svc_unit);
+    let unit_name: UnitName = "test.service".parse()?;
     // create /etc/systemd/system/test.service
-    create_unit_configuration_file("test.service", svc_unit_literal.as_bytes())?;
+    create_unit_configuration_file(unit_name.as_str(), svc_unit_literal.as_bytes())?;
     let client = manager::build_blocking_proxy()?;
-    let job_path = client.start_unit("test.service", "replace")?;
+    let job_path = client.start_unit(&unit_name, "replace")?;
     println!("{}", job_path.as_str());
-    let svc_unit_path = client.get_unit("test.service")?;
+    let svc_unit_path = client.get_unit(&unit_name)?;
     println!("{}", svc_unit_path.as_str());
     // verify unit state given unit path
     let client = unit::build_blocking_proxy(svc_unit_path)?;
     let unit_props = client.get_properties()?;
-    let unit_props: UnitProps = unit_props.into();
     println!("{:?}", unit_props);
     assert_eq!(unit_props.load_state, UnitLoadStateType::Loaded);
     assert_eq!(unit_props.active_state, UnitActiveStateType::Active);
@@ -37,7 +37,6 @@ fn main() -> Result<()> {
     std::thread::sleep(std::time::Duration::from_secs(4));
     // service should exit after 3 sec
     let unit_props = client.get_properties()?;
-    let unit_props: UnitProps = unit_props.into();
     println!("{:?}", unit_props);
     assert_eq!(unit_props.load_state, UnitLoadStateType::Loaded);
     assert_eq!(unit_props.active_state, UnitActiveStateType::Inactive);
@@ -1,6 +1,6 @@
 use systemd_client::{
     create_unit_configuration_file, manager, unit, Result, ServiceConfiguration,
-    ServiceUnitConfiguration, UnitActiveStateType, UnitConfiguration, UnitLoadStateType,
+    ServiceUnitConfiguration, UnitActiveStateType, UnitConfiguration, UnitLoadStateType, UnitName,
     UnitSubStateType,
 };
 
@@ -20,12 +20,13 @@ async fn main() -> Result<()> {
         .service(svc_builder)
         .build();
     let svc_unit_literal = format!("{}", svc_unit);
+    let unit_name: UnitName = "test.service".parse()?;
     // create /etc/systemd/system/test.service
-    create_unit_configuration_file("test.service", svc_unit_literal.as_bytes())?;
+    create_unit_configuration_file(unit_name.as_str(), svc_unit_literal.as_bytes())?;
     let client = manager::build_nonblock_proxy().await?;
-    let job_path = client.start_unit("test.service", "replace").await?;
+    let job_path = client.start_unit(&unit_name, "replace").await?;
     println!("{}", job_path.as_str());
-    let svc_unit_path = client.get_unit("test.service").await?;
+    let svc_unit_path = client.get_unit(&unit_name).await?;
     println!("{}", svc_unit_path.as_str());
     // verify unit state given unit path
     let client = unit::build_nonblock_proxy(svc_unit_path).await?;
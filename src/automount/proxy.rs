@@ -0,0 +1,70 @@
+use crate::{AutomountProps, Result, Unit, UnitType};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Automount",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdAutomount {
+    #[dbus_proxy(property, name = "Where")]
+    fn where_(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "TimeoutIdleUSec")]
+    fn timeout_idle_usec(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+}
+
+impl SystemdAutomountProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<AutomountProps> {
+        let automount_props = AutomountProps::builder()
+            .where_(self.where_()?)
+            .timeout_idle(self.timeout_idle_usec()?)
+            .result(self.result()?)
+            .build();
+        Ok(automount_props)
+    }
+}
+
+impl SystemdAutomountProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<AutomountProps> {
+        let automount_props = AutomountProps::builder()
+            .where_(self.where_().await?)
+            .timeout_idle(self.timeout_idle_usec().await?)
+            .result(self.result().await?)
+            .build();
+        Ok(automount_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdAutomountProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdAutomountProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdAutomountProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdAutomountProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
+
+// proxy of a automount unit from the manager's unit listing
+pub async fn build_nonblock_proxy_from_unit(unit: &Unit) -> Result<SystemdAutomountProxy<'static>> {
+    unit.check_unit_type(UnitType::Automount)?;
+    build_nonblock_proxy(unit.object_path.clone()).await
+}
+
+pub fn build_blocking_proxy_from_unit(
+    unit: &Unit,
+) -> Result<SystemdAutomountProxyBlocking<'static>> {
+    unit.check_unit_type(UnitType::Automount)?;
+    build_blocking_proxy(unit.object_path.clone())
+}
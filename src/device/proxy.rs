@@ -0,0 +1,49 @@
+use crate::{DeviceProps, Result};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Device",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdDevice {
+    #[dbus_proxy(property, name = "SysFSPath")]
+    fn sysfs_path(&self) -> zbus::Result<String>;
+}
+
+impl SystemdDeviceProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<DeviceProps> {
+        let device_props = DeviceProps::builder()
+            .sysfs_path(self.sysfs_path()?)
+            .build();
+        Ok(device_props)
+    }
+}
+
+impl SystemdDeviceProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<DeviceProps> {
+        let device_props = DeviceProps::builder()
+            .sysfs_path(self.sysfs_path().await?)
+            .build();
+        Ok(device_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdDeviceProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdDeviceProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdDeviceProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdDeviceProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
@@ -21,6 +21,26 @@ pub enum ErrorImpl {
     IntoString(#[from] std::ffi::IntoStringError),
     #[error("io error, detail: {0:?}")]
     Io(#[from] std::io::Error),
+    #[error("unknown unit type, unit name: {0}")]
+    UnknownUnitType(String),
+    #[error("invalid unit name {0:?}, detail: {1}")]
+    InvalidUnitName(String, &'static str),
+    #[error("invalid escape sequence in {0:?}")]
+    InvalidEscape(String),
+    #[error("invalid path {0:?}")]
+    InvalidPath(String),
+    #[error("invalid value {0:?} for {1}")]
+    InvalidValue(String, &'static str),
+    #[error("parse error at line {0}, detail: {1}")]
+    Parse(usize, String),
+    #[error("invalid unit file {0:?}, detail: {1}")]
+    InvalidUnitFile(String, String),
+}
+
+impl From<ErrorImpl> for Error {
+    fn from(err: ErrorImpl) -> Self {
+        Error(Box::new(err))
+    }
 }
 
 impl From<zbus::Error> for Error {
@@ -1,14 +1,33 @@
+pub mod automount;
 mod constants;
+pub mod device;
 pub mod errors;
+pub mod lookup;
 pub mod manager;
 pub mod models;
+pub mod mount;
+pub mod names;
+pub mod parser;
+pub mod path;
+pub mod scope;
+pub mod service;
+pub mod slice;
+pub mod socket;
+pub mod swap;
+pub mod target;
 pub mod templates;
+pub mod timer;
 pub mod unit;
 pub mod utils;
+pub mod validation;
 
 pub(crate) use constants::*;
 pub use errors::*;
+pub use lookup::*;
 pub use models::*;
+pub use names::*;
+pub use parser::*;
 pub use templates::*;
 pub use unit::*;
 pub use utils::*;
+pub use validation::*;
@@ -0,0 +1,226 @@
+use crate::{ErrorImpl, Result, UnitDocument, UnitName};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+// unit search path, directories ordered from highest to lowest precedence
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#Unit%20File%20Load%20Path
+pub struct LookupPaths {
+    pub directories: Vec<PathBuf>,
+}
+
+impl LookupPaths {
+    pub fn new(directories: Vec<PathBuf>) -> LookupPaths {
+        LookupPaths { directories }
+    }
+
+    pub fn system() -> LookupPaths {
+        let directories = [
+            "/etc/systemd/system.control",
+            "/run/systemd/system.control",
+            "/run/systemd/transient",
+            "/run/systemd/generator.early",
+            "/etc/systemd/system",
+            "/etc/systemd/system.attached",
+            "/run/systemd/system",
+            "/run/systemd/system.attached",
+            "/run/systemd/generator",
+            "/usr/local/lib/systemd/system",
+            "/usr/lib/systemd/system",
+            "/lib/systemd/system",
+            "/run/systemd/generator.late",
+        ];
+        LookupPaths::new(directories.iter().map(PathBuf::from).collect())
+    }
+
+    // directories of the calling user's service manager, as systemd builds them from the XDG
+    // base directories, runtime directories are left out without $XDG_RUNTIME_DIR
+    // https://specifications.freedesktop.org/basedir-spec/latest/
+    pub fn user() -> LookupPaths {
+        let home = absolute_var("HOME");
+        let xdg_home = |key: &str, fallback: &str| {
+            absolute_var(key).or_else(|| home.as_ref().map(|home| home.join(fallback)))
+        };
+        let config_home = xdg_home("XDG_CONFIG_HOME", ".config");
+        let data_home = xdg_home("XDG_DATA_HOME", ".local/share");
+        let runtime_dir = absolute_var("XDG_RUNTIME_DIR");
+        let config_dirs = absolute_vars("XDG_CONFIG_DIRS", "/etc/xdg");
+        let data_dirs = absolute_vars("XDG_DATA_DIRS", "/usr/local/share:/usr/share");
+        let runtime = |name: &str| {
+            runtime_dir
+                .as_ref()
+                .map(|dir| dir.join("systemd").join(name))
+        };
+        let mut directories = vec![];
+        directories.extend(
+            config_home
+                .as_ref()
+                .map(|dir| dir.join("systemd/user.control")),
+        );
+        directories.extend(runtime("user.control"));
+        directories.extend(runtime("transient"));
+        directories.extend(runtime("generator.early"));
+        directories.extend(config_home.as_ref().map(|dir| dir.join("systemd/user")));
+        directories.extend(config_dirs.iter().map(|dir| dir.join("systemd/user")));
+        directories.push(PathBuf::from("/etc/systemd/user"));
+        directories.extend(runtime("user"));
+        directories.push(PathBuf::from("/run/systemd/user"));
+        directories.extend(runtime("generator"));
+        directories.extend(data_home.as_ref().map(|dir| dir.join("systemd/user")));
+        directories.extend(data_dirs.iter().map(|dir| dir.join("systemd/user")));
+        directories.push(PathBuf::from("/usr/local/lib/systemd/user"));
+        directories.push(PathBuf::from("/usr/lib/systemd/user"));
+        directories.extend(runtime("generator.late"));
+        // e.g. /usr/local/share in $XDG_DATA_DIRS twice
+        let mut seen = HashSet::new();
+        directories.retain(|directory| seen.insert(directory.clone()));
+        LookupPaths::new(directories)
+    }
+
+    // first unit file named after the unit, or after its template for an instance
+    fn find_fragment(&self, unit_name: &UnitName) -> Option<PathBuf> {
+        let template = unit_name.template();
+        let names = std::iter::once(unit_name).chain(template.as_ref());
+        for name in names {
+            for directory in self.directories.iter() {
+                let path = directory.join(name.as_str());
+                // symlink_metadata, so a dangling mask symlink is found as well
+                if path.symlink_metadata().is_ok() {
+                    return Some(path);
+                }
+            }
+        }
+        None
+    }
+
+    // <unit>.d, <template>.d, then e.g. "foo-.service.d" for "foo-bar.service" and finally "service.d"
+    fn drop_in_directory_names(unit_name: &UnitName) -> Vec<String> {
+        let unit_type = unit_name.unit_type();
+        let mut names = vec![format!("{}.d", unit_name)];
+        if let Some(template) = unit_name.template() {
+            names.push(format!("{}.d", template));
+        }
+        let prefix = unit_name.prefix();
+        for (position, _) in prefix.rmatch_indices('-') {
+            names.push(format!("{}-.{}.d", &prefix[..position], unit_type));
+        }
+        names.push(format!("{}.d", unit_type));
+        names
+    }
+
+    // drop-ins in the order systemd applies them: sorted by file name, where a file overrides
+    // same-named ones in directories of lower precedence
+    fn find_drop_ins(&self, unit_name: &UnitName) -> Result<Vec<PathBuf>> {
+        let names = LookupPaths::drop_in_directory_names(unit_name);
+        let mut seen = HashSet::new();
+        let mut drop_ins = vec![];
+        for directory in self.directories.iter() {
+            for name in names.iter() {
+                let entries = match std::fs::read_dir(directory.join(name)) {
+                    Ok(entries) => entries,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                    Err(err) => return Err(err.into()),
+                };
+                let mut paths = vec![];
+                for entry in entries {
+                    let path = entry?.path();
+                    if path
+                        .extension()
+                        .is_some_and(|extension| extension == "conf")
+                    {
+                        paths.push(path);
+                    }
+                }
+                paths.sort();
+                for path in paths {
+                    if let Some(file_name) = path.file_name() {
+                        if seen.insert(file_name.to_owned()) {
+                            drop_ins.push(path);
+                        }
+                    }
+                }
+            }
+        }
+        drop_ins.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+        // masked drop-ins only hide same-named ones, empty ones are still listed
+        drop_ins.retain(|path| !is_null_link(path));
+        Ok(drop_ins)
+    }
+
+    // fragment and drop-ins of a unit merged like `systemctl cat`, each section keeps its origin
+    pub fn load_effective_configuration(
+        &self,
+        unit_name: &UnitName,
+    ) -> Result<EffectiveConfiguration> {
+        let fragment = self.find_fragment(unit_name);
+        let masked = fragment.as_deref().is_some_and(is_masked);
+        let mut document = UnitDocument::new();
+        if masked {
+            return Ok(EffectiveConfiguration {
+                fragment,
+                drop_ins: vec![],
+                masked,
+                document,
+            });
+        }
+        if let Some(fragment) = fragment.as_deref() {
+            document.merge(read_document(fragment)?);
+        }
+        let drop_ins = self.find_drop_ins(unit_name)?;
+        for drop_in in drop_ins.iter() {
+            document.merge(read_document(drop_in)?);
+        }
+        Ok(EffectiveConfiguration {
+            fragment,
+            drop_ins,
+            masked,
+            document,
+        })
+    }
+}
+
+pub struct EffectiveConfiguration {
+    pub fragment: Option<PathBuf>,
+    pub drop_ins: Vec<PathBuf>,
+    // the fragment is a symlink to /dev/null or an empty file
+    pub masked: bool,
+    pub document: UnitDocument,
+}
+
+// empty and relative values are ignored, like systemd does
+fn absolute_var(key: &str) -> Option<PathBuf> {
+    std::env::var_os(key)
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+}
+
+// colon separated list, the default applies if no entry is usable
+fn absolute_vars(key: &str, default: &str) -> Vec<PathBuf> {
+    let value = std::env::var_os(key).unwrap_or_default();
+    let directories: Vec<PathBuf> = std::env::split_paths(&value)
+        .filter(|path| path.is_absolute())
+        .collect();
+    match directories.is_empty() {
+        true => std::env::split_paths(default).collect(),
+        false => directories,
+    }
+}
+
+fn is_null_link(path: &Path) -> bool {
+    std::fs::read_link(path).is_ok_and(|target| target == Path::new("/dev/null"))
+}
+
+fn is_masked(path: &Path) -> bool {
+    match std::fs::read_link(path) {
+        Ok(target) => target == Path::new("/dev/null"),
+        Err(_) => path.metadata().is_ok_and(|metadata| metadata.len() == 0),
+    }
+}
+
+fn read_document(path: &Path) -> Result<UnitDocument> {
+    let content = std::fs::read_to_string(path)?;
+    let mut document: UnitDocument = content.parse().map_err(|err: crate::Error| {
+        ErrorImpl::InvalidUnitFile(path.display().to_string(), err.to_string())
+    })?;
+    document.set_origin(path);
+    Ok(document)
+}
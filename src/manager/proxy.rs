@@ -1,4 +1,4 @@
-use crate::{Result, UnitTuple};
+use crate::{Result, UnitName, UnitTuple};
 
 #[zbus::dbus_proxy(
     interface = "org.freedesktop.systemd1.Manager",
@@ -6,13 +6,13 @@ use crate::{Result, UnitTuple};
     default_path = "/org/freedesktop/systemd1"
 )]
 trait SystemdManager {
-    fn get_unit(&self, name: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn get_unit(&self, name: &UnitName) -> zbus::Result<zvariant::OwnedObjectPath>;
     fn list_units(&self) -> zbus::Result<Vec<UnitTuple>>;
-    fn load_unit(&self, name: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
-    fn reload_unit(&self, name: &str, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
-    fn restart_unit(&self, name: &str, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
-    fn start_unit(&self, name: &str, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
-    fn stop_unit(&self, name: &str, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn load_unit(&self, name: &UnitName) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn reload_unit(&self, name: &UnitName, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn restart_unit(&self, name: &UnitName, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn start_unit(&self, name: &UnitName, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn stop_unit(&self, name: &UnitName, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
     #[dbus_proxy(property)]
     fn architecture(&self) -> zbus::Result<String>;
     #[dbus_proxy(property)]
@@ -1,3 +1,7 @@
+use crate::{ErrorImpl, Result, UnitName, UnitType};
+use std::fmt::Display;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
 // systemctl --state=help
 #[derive(Clone, Debug, PartialEq)]
 pub enum UnitLoadStateType {
@@ -24,17 +28,18 @@ impl From<String> for UnitLoadStateType {
     }
 }
 
-impl ToString for UnitLoadStateType {
-    fn to_string(&self) -> String {
-        match self {
-            UnitLoadStateType::Stub => String::from("stub"),
-            UnitLoadStateType::Loaded => String::from("loaded"),
-            UnitLoadStateType::NotFound => String::from("not-found"),
-            UnitLoadStateType::Error => String::from("error"),
-            UnitLoadStateType::Merged => String::from("merged"),
-            UnitLoadStateType::Masked => String::from("masked"),
-            UnitLoadStateType::Other(other) => other.to_owned(),
-        }
+impl Display for UnitLoadStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            UnitLoadStateType::Stub => "stub",
+            UnitLoadStateType::Loaded => "loaded",
+            UnitLoadStateType::NotFound => "not-found",
+            UnitLoadStateType::Error => "error",
+            UnitLoadStateType::Merged => "merged",
+            UnitLoadStateType::Masked => "masked",
+            UnitLoadStateType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", state)
     }
 }
 
@@ -63,17 +68,18 @@ impl From<String> for UnitActiveStateType {
     }
 }
 
-impl ToString for UnitActiveStateType {
-    fn to_string(&self) -> String {
-        match self {
-            UnitActiveStateType::Active => String::from("active"),
-            UnitActiveStateType::Activating => String::from("activating"),
-            UnitActiveStateType::Deactivating => String::from("deactivating"),
-            UnitActiveStateType::Failed => String::from("failed"),
-            UnitActiveStateType::Inactive => String::from("inactive"),
-            UnitActiveStateType::Reloading => String::from("reloading"),
-            UnitActiveStateType::Other(other) => other.to_owned(),
-        }
+impl Display for UnitActiveStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            UnitActiveStateType::Active => "active",
+            UnitActiveStateType::Activating => "activating",
+            UnitActiveStateType::Deactivating => "deactivating",
+            UnitActiveStateType::Failed => "failed",
+            UnitActiveStateType::Inactive => "inactive",
+            UnitActiveStateType::Reloading => "reloading",
+            UnitActiveStateType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", state)
     }
 }
 
@@ -165,49 +171,50 @@ impl From<String> for UnitSubStateType {
     }
 }
 
-impl ToString for UnitSubStateType {
-    fn to_string(&self) -> String {
-        match self {
-            UnitSubStateType::Abandon => String::from("abandon"),
-            UnitSubStateType::Activating => String::from("activating"),
-            UnitSubStateType::ActivatingDone => String::from("activating-done"),
-            UnitSubStateType::Active => String::from("active"),
-            UnitSubStateType::AutoRestart => String::from("auto-restart"),
-            UnitSubStateType::Deactivating => String::from("deactivating"),
-            UnitSubStateType::DeactivatingSigterm => String::from("deactivating-sigkill"),
-            UnitSubStateType::DeactivatingSigkill => String::from("deactivating-sigkill"),
-            UnitSubStateType::Dead => String::from("dead"),
-            UnitSubStateType::Elapsed => String::from("elapsed"),
-            UnitSubStateType::Exited => String::from("exited"),
-            UnitSubStateType::Failed => String::from("failed"),
-            UnitSubStateType::FinalSigterm => String::from("final-sigterm"),
-            UnitSubStateType::FinalSigkill => String::from("final-sigkill"),
-            UnitSubStateType::Mounting => String::from("mounting"),
-            UnitSubStateType::MountingDone => String::from("mounting-done"),
-            UnitSubStateType::Mounted => String::from("mounted"),
-            UnitSubStateType::Plugged => String::from("plugged"),
-            UnitSubStateType::Listening => String::from("listening"),
-            UnitSubStateType::Reload => String::from("reload"),
-            UnitSubStateType::Remounting => String::from("remounting"),
-            UnitSubStateType::RemountingSigterm => String::from("remounting-sigterm"),
-            UnitSubStateType::RemountingSigkill => String::from("remounting-sigkill"),
-            UnitSubStateType::Running => String::from("running"),
-            UnitSubStateType::Start => String::from("start"),
-            UnitSubStateType::StartChown => String::from("start-chown"),
-            UnitSubStateType::StartPre => String::from("start-pre"),
-            UnitSubStateType::StartPost => String::from("start-post"),
-            UnitSubStateType::Stop => String::from("stop"),
-            UnitSubStateType::StopPost => String::from("stop-post"),
-            UnitSubStateType::StopSigabrt => String::from("stop-sigabrt"),
-            UnitSubStateType::StopSigterm => String::from("stop-sigterm"),
-            UnitSubStateType::StopSigkill => String::from("stop-sigkill"),
-            UnitSubStateType::Tentative => String::from("tentative"),
-            UnitSubStateType::Unmounting => String::from("unmounting"),
-            UnitSubStateType::UnmountingSigterm => String::from("unmounting-sigterm"),
-            UnitSubStateType::UnmountingSigkill => String::from("unmounting-sigkill"),
-            UnitSubStateType::Waiting => String::from("waiting"),
-            UnitSubStateType::Other(other) => other.to_owned(),
-        }
+impl Display for UnitSubStateType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = match self {
+            UnitSubStateType::Abandon => "abandon",
+            UnitSubStateType::Activating => "activating",
+            UnitSubStateType::ActivatingDone => "activating-done",
+            UnitSubStateType::Active => "active",
+            UnitSubStateType::AutoRestart => "auto-restart",
+            UnitSubStateType::Deactivating => "deactivating",
+            UnitSubStateType::DeactivatingSigterm => "deactivating-sigterm",
+            UnitSubStateType::DeactivatingSigkill => "deactivating-sigkill",
+            UnitSubStateType::Dead => "dead",
+            UnitSubStateType::Elapsed => "elapsed",
+            UnitSubStateType::Exited => "exited",
+            UnitSubStateType::Failed => "failed",
+            UnitSubStateType::FinalSigterm => "final-sigterm",
+            UnitSubStateType::FinalSigkill => "final-sigkill",
+            UnitSubStateType::Mounting => "mounting",
+            UnitSubStateType::MountingDone => "mounting-done",
+            UnitSubStateType::Mounted => "mounted",
+            UnitSubStateType::Plugged => "plugged",
+            UnitSubStateType::Listening => "listening",
+            UnitSubStateType::Reload => "reload",
+            UnitSubStateType::Remounting => "remounting",
+            UnitSubStateType::RemountingSigterm => "remounting-sigterm",
+            UnitSubStateType::RemountingSigkill => "remounting-sigkill",
+            UnitSubStateType::Running => "running",
+            UnitSubStateType::Start => "start",
+            UnitSubStateType::StartChown => "start-chown",
+            UnitSubStateType::StartPre => "start-pre",
+            UnitSubStateType::StartPost => "start-post",
+            UnitSubStateType::Stop => "stop",
+            UnitSubStateType::StopPost => "stop-post",
+            UnitSubStateType::StopSigabrt => "stop-sigabrt",
+            UnitSubStateType::StopSigterm => "stop-sigterm",
+            UnitSubStateType::StopSigkill => "stop-sigkill",
+            UnitSubStateType::Tentative => "tentative",
+            UnitSubStateType::Unmounting => "unmounting",
+            UnitSubStateType::UnmountingSigterm => "unmounting-sigterm",
+            UnitSubStateType::UnmountingSigkill => "unmounting-sigkill",
+            UnitSubStateType::Waiting => "waiting",
+            UnitSubStateType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", state)
     }
 }
 
@@ -268,6 +275,22 @@ impl From<UnitTuple> for Unit {
     }
 }
 
+impl Unit {
+    pub fn unit_name(&self) -> Result<UnitName> {
+        self.name.parse()
+    }
+
+    // a typed proxy only works for units of the matching type
+    pub(crate) fn check_unit_type(&self, unit_type: UnitType) -> Result<()> {
+        if self.unit_name()?.unit_type() != unit_type {
+            return Err(
+                ErrorImpl::InvalidUnitName(self.name.clone(), "unexpected unit type").into(),
+            );
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct UnitProps {
     pub id: String,
@@ -349,6 +372,1084 @@ impl UnitPropsBuilder {
     }
 }
 
+// result of the last run of a unit, shared by service, socket, mount, timer, ... units
+#[derive(Clone, Debug, PartialEq)]
+pub enum UnitResultType {
+    Success,
+    Resources,
+    Protocol,
+    Timeout,
+    ExitCode,
+    Signal,
+    CoreDump,
+    Watchdog,
+    StartLimitHit,
+    OomKill,
+    ExecCondition,
+    Other(String),
+}
+
+impl From<String> for UnitResultType {
+    fn from(origin: String) -> Self {
+        match origin.as_str() {
+            "success" => UnitResultType::Success,
+            "resources" => UnitResultType::Resources,
+            "protocol" => UnitResultType::Protocol,
+            "timeout" => UnitResultType::Timeout,
+            "exit-code" => UnitResultType::ExitCode,
+            "signal" => UnitResultType::Signal,
+            "core-dump" => UnitResultType::CoreDump,
+            "watchdog" => UnitResultType::Watchdog,
+            "start-limit-hit" => UnitResultType::StartLimitHit,
+            "oom-kill" => UnitResultType::OomKill,
+            "exec-condition" => UnitResultType::ExecCondition,
+            _ => UnitResultType::Other(origin),
+        }
+    }
+}
+
+impl Display for UnitResultType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let result = match self {
+            UnitResultType::Success => "success",
+            UnitResultType::Resources => "resources",
+            UnitResultType::Protocol => "protocol",
+            UnitResultType::Timeout => "timeout",
+            UnitResultType::ExitCode => "exit-code",
+            UnitResultType::Signal => "signal",
+            UnitResultType::CoreDump => "core-dump",
+            UnitResultType::Watchdog => "watchdog",
+            UnitResultType::StartLimitHit => "start-limit-hit",
+            UnitResultType::OomKill => "oom-kill",
+            UnitResultType::ExecCondition => "exec-condition",
+            UnitResultType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", result)
+    }
+}
+
+// systemd reports "unset" timestamps as 0 and unavailable counters as u64::MAX
+fn usec_to_system_time(usec: u64) -> Option<SystemTime> {
+    match usec {
+        0 | u64::MAX => None,
+        usec => Some(UNIX_EPOCH + Duration::from_micros(usec)),
+    }
+}
+
+fn usec_to_duration(usec: u64) -> Option<Duration> {
+    match usec {
+        0 | u64::MAX => None,
+        usec => Some(Duration::from_micros(usec)),
+    }
+}
+
+fn counter(value: u64) -> Option<u64> {
+    match value {
+        u64::MAX => None,
+        value => Some(value),
+    }
+}
+
+// (path, argv, ignore failure, start realtime, start monotonic, exit realtime, exit monotonic, pid, code, status)
+pub type ExecCommandTuple = (String, Vec<String>, bool, u64, u64, u64, u64, u32, i32, i32);
+
+#[derive(Clone, Debug)]
+pub struct ExecCommand {
+    pub path: String,
+    pub argv: Vec<String>,
+    pub ignore_failure: bool,
+    pub start_timestamp: Option<SystemTime>,
+    pub start_timestamp_monotonic: Option<Duration>,
+    pub exit_timestamp: Option<SystemTime>,
+    pub exit_timestamp_monotonic: Option<Duration>,
+    pub pid: u32,
+    pub code: i32,
+    pub status: i32,
+}
+
+impl From<ExecCommandTuple> for ExecCommand {
+    fn from(t: ExecCommandTuple) -> Self {
+        ExecCommand {
+            path: t.0,
+            argv: t.1,
+            ignore_failure: t.2,
+            start_timestamp: usec_to_system_time(t.3),
+            start_timestamp_monotonic: usec_to_duration(t.4),
+            exit_timestamp: usec_to_system_time(t.5),
+            exit_timestamp_monotonic: usec_to_duration(t.6),
+            pid: t.7,
+            code: t.8,
+            status: t.9,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Service%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct ServiceProps {
+    pub main_pid: u32,
+    pub control_pid: u32,
+    pub exec_main_status: i32,
+    pub exec_main_code: i32,
+    pub exec_main_start_timestamp: Option<SystemTime>,
+    pub n_restarts: u32,
+    pub result: UnitResultType,
+    pub ty: String,
+    pub restart: String,
+    pub status_text: String,
+    pub status_errno: i32,
+    pub watchdog_usec: Option<Duration>,
+    pub memory_current: Option<u64>,
+    pub cpu_usage_nsec: Option<u64>,
+    pub tasks_current: Option<u64>,
+    pub exec_start: Vec<ExecCommand>,
+}
+
+impl ServiceProps {
+    pub fn builder() -> ServicePropsBuilder {
+        ServicePropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct ServicePropsBuilder {
+    pub main_pid: Option<u32>,
+    pub control_pid: Option<u32>,
+    pub exec_main_status: Option<i32>,
+    pub exec_main_code: Option<i32>,
+    pub exec_main_start_timestamp: Option<Option<SystemTime>>,
+    pub n_restarts: Option<u32>,
+    pub result: Option<UnitResultType>,
+    pub ty: Option<String>,
+    pub restart: Option<String>,
+    pub status_text: Option<String>,
+    pub status_errno: Option<i32>,
+    pub watchdog_usec: Option<Option<Duration>>,
+    pub memory_current: Option<Option<u64>>,
+    pub cpu_usage_nsec: Option<Option<u64>>,
+    pub tasks_current: Option<Option<u64>>,
+    pub exec_start: Option<Vec<ExecCommand>>,
+}
+
+impl ServicePropsBuilder {
+    pub fn main_pid(mut self, main_pid: u32) -> Self {
+        self.main_pid = Some(main_pid);
+        self
+    }
+
+    pub fn control_pid(mut self, control_pid: u32) -> Self {
+        self.control_pid = Some(control_pid);
+        self
+    }
+
+    pub fn exec_main_status(mut self, exec_main_status: i32) -> Self {
+        self.exec_main_status = Some(exec_main_status);
+        self
+    }
+
+    pub fn exec_main_code(mut self, exec_main_code: i32) -> Self {
+        self.exec_main_code = Some(exec_main_code);
+        self
+    }
+
+    pub fn exec_main_start_timestamp(mut self, exec_main_start_timestamp: u64) -> Self {
+        self.exec_main_start_timestamp = Some(usec_to_system_time(exec_main_start_timestamp));
+        self
+    }
+
+    pub fn n_restarts(mut self, n_restarts: u32) -> Self {
+        self.n_restarts = Some(n_restarts);
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn ty(mut self, ty: String) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    pub fn restart(mut self, restart: String) -> Self {
+        self.restart = Some(restart);
+        self
+    }
+
+    pub fn status_text(mut self, status_text: String) -> Self {
+        self.status_text = Some(status_text);
+        self
+    }
+
+    pub fn status_errno(mut self, status_errno: i32) -> Self {
+        self.status_errno = Some(status_errno);
+        self
+    }
+
+    pub fn watchdog_usec(mut self, watchdog_usec: u64) -> Self {
+        self.watchdog_usec = Some(usec_to_duration(watchdog_usec));
+        self
+    }
+
+    pub fn memory_current(mut self, memory_current: u64) -> Self {
+        self.memory_current = Some(counter(memory_current));
+        self
+    }
+
+    pub fn cpu_usage_nsec(mut self, cpu_usage_nsec: u64) -> Self {
+        self.cpu_usage_nsec = Some(counter(cpu_usage_nsec));
+        self
+    }
+
+    pub fn tasks_current(mut self, tasks_current: u64) -> Self {
+        self.tasks_current = Some(counter(tasks_current));
+        self
+    }
+
+    pub fn exec_start(mut self, exec_start: Vec<ExecCommandTuple>) -> Self {
+        self.exec_start = Some(exec_start.into_iter().map(ExecCommand::from).collect());
+        self
+    }
+
+    pub fn build(self) -> ServiceProps {
+        ServiceProps {
+            main_pid: self.main_pid.expect("main pid undefined"),
+            control_pid: self.control_pid.expect("control pid undefined"),
+            exec_main_status: self.exec_main_status.expect("exec main status undefined"),
+            exec_main_code: self.exec_main_code.expect("exec main code undefined"),
+            exec_main_start_timestamp: self
+                .exec_main_start_timestamp
+                .expect("exec main start timestamp undefined"),
+            n_restarts: self.n_restarts.expect("n restarts undefined"),
+            result: self.result.expect("result undefined"),
+            ty: self.ty.expect("type undefined"),
+            restart: self.restart.expect("restart undefined"),
+            status_text: self.status_text.expect("status text undefined"),
+            status_errno: self.status_errno.expect("status errno undefined"),
+            watchdog_usec: self.watchdog_usec.expect("watchdog usec undefined"),
+            memory_current: self.memory_current.expect("memory current undefined"),
+            cpu_usage_nsec: self.cpu_usage_nsec.expect("cpu usage nsec undefined"),
+            tasks_current: self.tasks_current.expect("tasks current undefined"),
+            exec_start: self.exec_start.expect("exec start undefined"),
+        }
+    }
+}
+
+// (base, value, next elapse), e.g. ("OnUnitActiveUSec", 3600000000, 7200000000)
+pub type TimerMonotonicTuple = (String, u64, u64);
+
+#[derive(Clone, Debug)]
+pub struct TimerMonotonic {
+    pub base: String,
+    pub value: Duration,
+    pub next_elapse: Option<Duration>,
+}
+
+impl From<TimerMonotonicTuple> for TimerMonotonic {
+    fn from(t: TimerMonotonicTuple) -> Self {
+        TimerMonotonic {
+            base: t.0,
+            value: Duration::from_micros(t.1),
+            next_elapse: usec_to_duration(t.2),
+        }
+    }
+}
+
+// (base, calendar spec, next elapse), e.g. ("OnCalendar", "*-*-* 00:00:00", 1634601600000000)
+pub type TimerCalendarTuple = (String, String, u64);
+
+#[derive(Clone, Debug)]
+pub struct TimerCalendar {
+    pub base: String,
+    pub spec: String,
+    pub next_elapse: Option<SystemTime>,
+}
+
+impl From<TimerCalendarTuple> for TimerCalendar {
+    fn from(t: TimerCalendarTuple) -> Self {
+        TimerCalendar {
+            base: t.0,
+            spec: t.1,
+            next_elapse: usec_to_system_time(t.2),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Timer%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct TimerProps {
+    pub unit: String,
+    pub timers_monotonic: Vec<TimerMonotonic>,
+    pub timers_calendar: Vec<TimerCalendar>,
+    // wall clock time of the next elapse, None if no calendar timer is pending
+    pub next_elapse_realtime: Option<SystemTime>,
+    // CLOCK_MONOTONIC time of the next elapse, None if no monotonic timer is pending
+    pub next_elapse_monotonic: Option<Duration>,
+    pub last_trigger: Option<SystemTime>,
+    pub result: UnitResultType,
+    pub persistent: bool,
+}
+
+impl TimerProps {
+    pub fn builder() -> TimerPropsBuilder {
+        TimerPropsBuilder::default()
+    }
+
+    // wall clock time of the next elapse like `systemctl list-timers`, the monotonic elapse is
+    // offset by the given boot time, see `boot_time`
+    pub fn next_elapse(&self, boot_time: SystemTime) -> Option<SystemTime> {
+        let monotonic = self
+            .next_elapse_monotonic
+            .map(|monotonic| boot_time + monotonic);
+        match (self.next_elapse_realtime, monotonic) {
+            (Some(realtime), Some(monotonic)) => Some(realtime.min(monotonic)),
+            (realtime, monotonic) => realtime.or(monotonic),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct TimerPropsBuilder {
+    pub unit: Option<String>,
+    pub timers_monotonic: Option<Vec<TimerMonotonic>>,
+    pub timers_calendar: Option<Vec<TimerCalendar>>,
+    pub next_elapse_realtime: Option<Option<SystemTime>>,
+    pub next_elapse_monotonic: Option<Option<Duration>>,
+    pub last_trigger: Option<Option<SystemTime>>,
+    pub result: Option<UnitResultType>,
+    pub persistent: Option<bool>,
+}
+
+impl TimerPropsBuilder {
+    pub fn unit(mut self, unit: String) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn timers_monotonic(mut self, timers_monotonic: Vec<TimerMonotonicTuple>) -> Self {
+        self.timers_monotonic = Some(
+            timers_monotonic
+                .into_iter()
+                .map(TimerMonotonic::from)
+                .collect(),
+        );
+        self
+    }
+
+    pub fn timers_calendar(mut self, timers_calendar: Vec<TimerCalendarTuple>) -> Self {
+        self.timers_calendar = Some(
+            timers_calendar
+                .into_iter()
+                .map(TimerCalendar::from)
+                .collect(),
+        );
+        self
+    }
+
+    pub fn next_elapse_realtime(mut self, next_elapse_realtime: u64) -> Self {
+        self.next_elapse_realtime = Some(usec_to_system_time(next_elapse_realtime));
+        self
+    }
+
+    pub fn next_elapse_monotonic(mut self, next_elapse_monotonic: u64) -> Self {
+        self.next_elapse_monotonic = Some(usec_to_duration(next_elapse_monotonic));
+        self
+    }
+
+    pub fn last_trigger(mut self, last_trigger: u64) -> Self {
+        self.last_trigger = Some(usec_to_system_time(last_trigger));
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn persistent(mut self, persistent: bool) -> Self {
+        self.persistent = Some(persistent);
+        self
+    }
+
+    pub fn build(self) -> TimerProps {
+        TimerProps {
+            unit: self.unit.expect("unit undefined"),
+            timers_monotonic: self.timers_monotonic.expect("timers monotonic undefined"),
+            timers_calendar: self.timers_calendar.expect("timers calendar undefined"),
+            next_elapse_realtime: self
+                .next_elapse_realtime
+                .expect("next elapse realtime undefined"),
+            next_elapse_monotonic: self
+                .next_elapse_monotonic
+                .expect("next elapse monotonic undefined"),
+            last_trigger: self.last_trigger.expect("last trigger undefined"),
+            result: self.result.expect("result undefined"),
+            persistent: self.persistent.expect("persistent undefined"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SocketListenType {
+    Stream,
+    Datagram,
+    SequentialPacket,
+    Fifo,
+    Special,
+    Netlink,
+    MessageQueue,
+    UsbFunction,
+    Other(String),
+}
+
+impl From<String> for SocketListenType {
+    fn from(origin: String) -> Self {
+        match origin.as_str() {
+            "Stream" => SocketListenType::Stream,
+            "Datagram" => SocketListenType::Datagram,
+            "SequentialPacket" => SocketListenType::SequentialPacket,
+            "FIFO" => SocketListenType::Fifo,
+            "Special" => SocketListenType::Special,
+            "Netlink" => SocketListenType::Netlink,
+            "MessageQueue" => SocketListenType::MessageQueue,
+            "USBFunction" => SocketListenType::UsbFunction,
+            _ => SocketListenType::Other(origin),
+        }
+    }
+}
+
+impl Display for SocketListenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ty = match self {
+            SocketListenType::Stream => "Stream",
+            SocketListenType::Datagram => "Datagram",
+            SocketListenType::SequentialPacket => "SequentialPacket",
+            SocketListenType::Fifo => "FIFO",
+            SocketListenType::Special => "Special",
+            SocketListenType::Netlink => "Netlink",
+            SocketListenType::MessageQueue => "MessageQueue",
+            SocketListenType::UsbFunction => "USBFunction",
+            SocketListenType::Other(other) => other.as_str(),
+        };
+        write!(f, "{}", ty)
+    }
+}
+
+// (type, address), e.g. ("Stream", "[::]:22")
+pub type SocketListenTuple = (String, String);
+
+#[derive(Clone, Debug)]
+pub struct SocketListen {
+    pub ty: SocketListenType,
+    pub address: String,
+}
+
+impl From<SocketListenTuple> for SocketListen {
+    fn from(t: SocketListenTuple) -> Self {
+        SocketListen {
+            ty: t.0.into(),
+            address: t.1,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Socket%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct SocketProps {
+    pub listen: Vec<SocketListen>,
+    pub accept: bool,
+    pub n_connections: u32,
+    pub n_accepted: u32,
+    pub n_refused: u32,
+    pub result: UnitResultType,
+    pub control_pid: u32,
+    pub backlog: u32,
+    pub max_connections: u32,
+    pub socket_mode: u32,
+    pub socket_user: String,
+    pub socket_group: String,
+    pub file_descriptor_name: String,
+}
+
+impl SocketProps {
+    pub fn builder() -> SocketPropsBuilder {
+        SocketPropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct SocketPropsBuilder {
+    pub listen: Option<Vec<SocketListen>>,
+    pub accept: Option<bool>,
+    pub n_connections: Option<u32>,
+    pub n_accepted: Option<u32>,
+    pub n_refused: Option<u32>,
+    pub result: Option<UnitResultType>,
+    pub control_pid: Option<u32>,
+    pub backlog: Option<u32>,
+    pub max_connections: Option<u32>,
+    pub socket_mode: Option<u32>,
+    pub socket_user: Option<String>,
+    pub socket_group: Option<String>,
+    pub file_descriptor_name: Option<String>,
+}
+
+impl SocketPropsBuilder {
+    pub fn listen(mut self, listen: Vec<SocketListenTuple>) -> Self {
+        self.listen = Some(listen.into_iter().map(SocketListen::from).collect());
+        self
+    }
+
+    pub fn accept(mut self, accept: bool) -> Self {
+        self.accept = Some(accept);
+        self
+    }
+
+    pub fn n_connections(mut self, n_connections: u32) -> Self {
+        self.n_connections = Some(n_connections);
+        self
+    }
+
+    pub fn n_accepted(mut self, n_accepted: u32) -> Self {
+        self.n_accepted = Some(n_accepted);
+        self
+    }
+
+    pub fn n_refused(mut self, n_refused: u32) -> Self {
+        self.n_refused = Some(n_refused);
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn control_pid(mut self, control_pid: u32) -> Self {
+        self.control_pid = Some(control_pid);
+        self
+    }
+
+    pub fn backlog(mut self, backlog: u32) -> Self {
+        self.backlog = Some(backlog);
+        self
+    }
+
+    pub fn max_connections(mut self, max_connections: u32) -> Self {
+        self.max_connections = Some(max_connections);
+        self
+    }
+
+    pub fn socket_mode(mut self, socket_mode: u32) -> Self {
+        self.socket_mode = Some(socket_mode);
+        self
+    }
+
+    pub fn socket_user(mut self, socket_user: String) -> Self {
+        self.socket_user = Some(socket_user);
+        self
+    }
+
+    pub fn socket_group(mut self, socket_group: String) -> Self {
+        self.socket_group = Some(socket_group);
+        self
+    }
+
+    pub fn file_descriptor_name(mut self, file_descriptor_name: String) -> Self {
+        self.file_descriptor_name = Some(file_descriptor_name);
+        self
+    }
+
+    pub fn build(self) -> SocketProps {
+        SocketProps {
+            listen: self.listen.expect("listen undefined"),
+            accept: self.accept.expect("accept undefined"),
+            n_connections: self.n_connections.expect("n connections undefined"),
+            n_accepted: self.n_accepted.expect("n accepted undefined"),
+            n_refused: self.n_refused.expect("n refused undefined"),
+            result: self.result.expect("result undefined"),
+            control_pid: self.control_pid.expect("control pid undefined"),
+            backlog: self.backlog.expect("backlog undefined"),
+            max_connections: self.max_connections.expect("max connections undefined"),
+            socket_mode: self.socket_mode.expect("socket mode undefined"),
+            socket_user: self.socket_user.expect("socket user undefined"),
+            socket_group: self.socket_group.expect("socket group undefined"),
+            file_descriptor_name: self
+                .file_descriptor_name
+                .expect("file descriptor name undefined"),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Mount%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct MountProps {
+    pub where_: String,
+    pub what: String,
+    pub options: String,
+    pub ty: String,
+    pub result: UnitResultType,
+    pub control_pid: u32,
+}
+
+impl MountProps {
+    pub fn builder() -> MountPropsBuilder {
+        MountPropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct MountPropsBuilder {
+    pub where_: Option<String>,
+    pub what: Option<String>,
+    pub options: Option<String>,
+    pub ty: Option<String>,
+    pub result: Option<UnitResultType>,
+    pub control_pid: Option<u32>,
+}
+
+impl MountPropsBuilder {
+    pub fn where_(mut self, where_: String) -> Self {
+        self.where_ = Some(where_);
+        self
+    }
+
+    pub fn what(mut self, what: String) -> Self {
+        self.what = Some(what);
+        self
+    }
+
+    pub fn options(mut self, options: String) -> Self {
+        self.options = Some(options);
+        self
+    }
+
+    pub fn ty(mut self, ty: String) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn control_pid(mut self, control_pid: u32) -> Self {
+        self.control_pid = Some(control_pid);
+        self
+    }
+
+    pub fn build(self) -> MountProps {
+        MountProps {
+            where_: self.where_.expect("where undefined"),
+            what: self.what.expect("what undefined"),
+            options: self.options.expect("options undefined"),
+            ty: self.ty.expect("type undefined"),
+            result: self.result.expect("result undefined"),
+            control_pid: self.control_pid.expect("control pid undefined"),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Automount%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct AutomountProps {
+    pub where_: String,
+    pub timeout_idle: Option<Duration>,
+    pub result: UnitResultType,
+}
+
+impl AutomountProps {
+    pub fn builder() -> AutomountPropsBuilder {
+        AutomountPropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct AutomountPropsBuilder {
+    pub where_: Option<String>,
+    pub timeout_idle: Option<Option<Duration>>,
+    pub result: Option<UnitResultType>,
+}
+
+impl AutomountPropsBuilder {
+    pub fn where_(mut self, where_: String) -> Self {
+        self.where_ = Some(where_);
+        self
+    }
+
+    pub fn timeout_idle(mut self, timeout_idle: u64) -> Self {
+        self.timeout_idle = Some(usec_to_duration(timeout_idle));
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn build(self) -> AutomountProps {
+        AutomountProps {
+            where_: self.where_.expect("where undefined"),
+            timeout_idle: self.timeout_idle.expect("timeout idle undefined"),
+            result: self.result.expect("result undefined"),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Swap%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct SwapProps {
+    pub what: String,
+    pub priority: i32,
+    pub result: UnitResultType,
+    pub control_pid: u32,
+}
+
+impl SwapProps {
+    pub fn builder() -> SwapPropsBuilder {
+        SwapPropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct SwapPropsBuilder {
+    pub what: Option<String>,
+    pub priority: Option<i32>,
+    pub result: Option<UnitResultType>,
+    pub control_pid: Option<u32>,
+}
+
+impl SwapPropsBuilder {
+    pub fn what(mut self, what: String) -> Self {
+        self.what = Some(what);
+        self
+    }
+
+    pub fn priority(mut self, priority: i32) -> Self {
+        self.priority = Some(priority);
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn control_pid(mut self, control_pid: u32) -> Self {
+        self.control_pid = Some(control_pid);
+        self
+    }
+
+    pub fn build(self) -> SwapProps {
+        SwapProps {
+            what: self.what.expect("what undefined"),
+            priority: self.priority.expect("priority undefined"),
+            result: self.result.expect("result undefined"),
+            control_pid: self.control_pid.expect("control pid undefined"),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Slice%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct SliceProps {
+    pub control_group: String,
+    pub memory_current: Option<u64>,
+    pub memory_peak: Option<u64>,
+    pub cpu_usage_nsec: Option<u64>,
+    pub tasks_current: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+    pub ip_ingress_bytes: Option<u64>,
+    pub ip_egress_bytes: Option<u64>,
+}
+
+impl SliceProps {
+    pub fn builder() -> SlicePropsBuilder {
+        SlicePropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct SlicePropsBuilder {
+    pub control_group: Option<String>,
+    pub memory_current: Option<Option<u64>>,
+    pub memory_peak: Option<Option<u64>>,
+    pub cpu_usage_nsec: Option<Option<u64>>,
+    pub tasks_current: Option<Option<u64>>,
+    pub io_read_bytes: Option<Option<u64>>,
+    pub io_write_bytes: Option<Option<u64>>,
+    pub ip_ingress_bytes: Option<Option<u64>>,
+    pub ip_egress_bytes: Option<Option<u64>>,
+}
+
+impl SlicePropsBuilder {
+    pub fn control_group(mut self, control_group: String) -> Self {
+        self.control_group = Some(control_group);
+        self
+    }
+
+    pub fn memory_current(mut self, memory_current: u64) -> Self {
+        self.memory_current = Some(counter(memory_current));
+        self
+    }
+
+    pub fn memory_peak(mut self, memory_peak: u64) -> Self {
+        self.memory_peak = Some(counter(memory_peak));
+        self
+    }
+
+    pub fn cpu_usage_nsec(mut self, cpu_usage_nsec: u64) -> Self {
+        self.cpu_usage_nsec = Some(counter(cpu_usage_nsec));
+        self
+    }
+
+    pub fn tasks_current(mut self, tasks_current: u64) -> Self {
+        self.tasks_current = Some(counter(tasks_current));
+        self
+    }
+
+    pub fn io_read_bytes(mut self, io_read_bytes: u64) -> Self {
+        self.io_read_bytes = Some(counter(io_read_bytes));
+        self
+    }
+
+    pub fn io_write_bytes(mut self, io_write_bytes: u64) -> Self {
+        self.io_write_bytes = Some(counter(io_write_bytes));
+        self
+    }
+
+    pub fn ip_ingress_bytes(mut self, ip_ingress_bytes: u64) -> Self {
+        self.ip_ingress_bytes = Some(counter(ip_ingress_bytes));
+        self
+    }
+
+    pub fn ip_egress_bytes(mut self, ip_egress_bytes: u64) -> Self {
+        self.ip_egress_bytes = Some(counter(ip_egress_bytes));
+        self
+    }
+
+    pub fn build(self) -> SliceProps {
+        SliceProps {
+            control_group: self.control_group.expect("control group undefined"),
+            memory_current: self.memory_current.expect("memory current undefined"),
+            memory_peak: self.memory_peak.expect("memory peak undefined"),
+            cpu_usage_nsec: self.cpu_usage_nsec.expect("cpu usage nsec undefined"),
+            tasks_current: self.tasks_current.expect("tasks current undefined"),
+            io_read_bytes: self.io_read_bytes.expect("io read bytes undefined"),
+            io_write_bytes: self.io_write_bytes.expect("io write bytes undefined"),
+            ip_ingress_bytes: self.ip_ingress_bytes.expect("ip ingress bytes undefined"),
+            ip_egress_bytes: self.ip_egress_bytes.expect("ip egress bytes undefined"),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Scope%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct ScopeProps {
+    pub control_group: String,
+    pub memory_current: Option<u64>,
+    pub memory_peak: Option<u64>,
+    pub cpu_usage_nsec: Option<u64>,
+    pub tasks_current: Option<u64>,
+    pub io_read_bytes: Option<u64>,
+    pub io_write_bytes: Option<u64>,
+    pub ip_ingress_bytes: Option<u64>,
+    pub ip_egress_bytes: Option<u64>,
+    pub result: UnitResultType,
+}
+
+impl ScopeProps {
+    pub fn builder() -> ScopePropsBuilder {
+        ScopePropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct ScopePropsBuilder {
+    pub control_group: Option<String>,
+    pub memory_current: Option<Option<u64>>,
+    pub memory_peak: Option<Option<u64>>,
+    pub cpu_usage_nsec: Option<Option<u64>>,
+    pub tasks_current: Option<Option<u64>>,
+    pub io_read_bytes: Option<Option<u64>>,
+    pub io_write_bytes: Option<Option<u64>>,
+    pub ip_ingress_bytes: Option<Option<u64>>,
+    pub ip_egress_bytes: Option<Option<u64>>,
+    pub result: Option<UnitResultType>,
+}
+
+impl ScopePropsBuilder {
+    pub fn control_group(mut self, control_group: String) -> Self {
+        self.control_group = Some(control_group);
+        self
+    }
+
+    pub fn memory_current(mut self, memory_current: u64) -> Self {
+        self.memory_current = Some(counter(memory_current));
+        self
+    }
+
+    pub fn memory_peak(mut self, memory_peak: u64) -> Self {
+        self.memory_peak = Some(counter(memory_peak));
+        self
+    }
+
+    pub fn cpu_usage_nsec(mut self, cpu_usage_nsec: u64) -> Self {
+        self.cpu_usage_nsec = Some(counter(cpu_usage_nsec));
+        self
+    }
+
+    pub fn tasks_current(mut self, tasks_current: u64) -> Self {
+        self.tasks_current = Some(counter(tasks_current));
+        self
+    }
+
+    pub fn io_read_bytes(mut self, io_read_bytes: u64) -> Self {
+        self.io_read_bytes = Some(counter(io_read_bytes));
+        self
+    }
+
+    pub fn io_write_bytes(mut self, io_write_bytes: u64) -> Self {
+        self.io_write_bytes = Some(counter(io_write_bytes));
+        self
+    }
+
+    pub fn ip_ingress_bytes(mut self, ip_ingress_bytes: u64) -> Self {
+        self.ip_ingress_bytes = Some(counter(ip_ingress_bytes));
+        self
+    }
+
+    pub fn ip_egress_bytes(mut self, ip_egress_bytes: u64) -> Self {
+        self.ip_egress_bytes = Some(counter(ip_egress_bytes));
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn build(self) -> ScopeProps {
+        ScopeProps {
+            control_group: self.control_group.expect("control group undefined"),
+            memory_current: self.memory_current.expect("memory current undefined"),
+            memory_peak: self.memory_peak.expect("memory peak undefined"),
+            cpu_usage_nsec: self.cpu_usage_nsec.expect("cpu usage nsec undefined"),
+            tasks_current: self.tasks_current.expect("tasks current undefined"),
+            io_read_bytes: self.io_read_bytes.expect("io read bytes undefined"),
+            io_write_bytes: self.io_write_bytes.expect("io write bytes undefined"),
+            ip_ingress_bytes: self.ip_ingress_bytes.expect("ip ingress bytes undefined"),
+            ip_egress_bytes: self.ip_egress_bytes.expect("ip egress bytes undefined"),
+            result: self.result.expect("result undefined"),
+        }
+    }
+}
+
+// (condition, path), e.g. ("PathExists", "/run/foo")
+pub type PathSpecTuple = (String, String);
+
+#[derive(Clone, Debug)]
+pub struct PathSpec {
+    pub condition: String,
+    pub path: String,
+}
+
+impl From<PathSpecTuple> for PathSpec {
+    fn from(t: PathSpecTuple) -> Self {
+        PathSpec {
+            condition: t.0,
+            path: t.1,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Path%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct PathProps {
+    pub unit: String,
+    pub paths: Vec<PathSpec>,
+    pub make_directory: bool,
+    pub result: UnitResultType,
+}
+
+impl PathProps {
+    pub fn builder() -> PathPropsBuilder {
+        PathPropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct PathPropsBuilder {
+    pub unit: Option<String>,
+    pub paths: Option<Vec<PathSpec>>,
+    pub make_directory: Option<bool>,
+    pub result: Option<UnitResultType>,
+}
+
+impl PathPropsBuilder {
+    pub fn unit(mut self, unit: String) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn paths(mut self, paths: Vec<PathSpecTuple>) -> Self {
+        self.paths = Some(paths.into_iter().map(PathSpec::from).collect());
+        self
+    }
+
+    pub fn make_directory(mut self, make_directory: bool) -> Self {
+        self.make_directory = Some(make_directory);
+        self
+    }
+
+    pub fn result(mut self, result: String) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    pub fn build(self) -> PathProps {
+        PathProps {
+            unit: self.unit.expect("unit undefined"),
+            paths: self.paths.expect("paths undefined"),
+            make_directory: self.make_directory.expect("make directory undefined"),
+            result: self.result.expect("result undefined"),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/org.freedesktop.systemd1.html#Device%20Unit%20Objects
+#[derive(Clone, Debug)]
+pub struct DeviceProps {
+    pub sysfs_path: String,
+}
+
+impl DeviceProps {
+    pub fn builder() -> DevicePropsBuilder {
+        DevicePropsBuilder::default()
+    }
+}
+
+#[derive(Default)]
+pub struct DevicePropsBuilder {
+    pub sysfs_path: Option<String>,
+}
+
+impl DevicePropsBuilder {
+    pub fn sysfs_path(mut self, sysfs_path: String) -> Self {
+        self.sysfs_path = Some(sysfs_path);
+        self
+    }
+
+    pub fn build(self) -> DeviceProps {
+        DeviceProps {
+            sysfs_path: self.sysfs_path.expect("sysfs path undefined"),
+        }
+    }
+}
+
 /*
 impl IntoModel<UnitProps> for arg::PropMap {
     fn into_model(self) -> Result<UnitProps> {
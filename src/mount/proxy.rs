@@ -0,0 +1,80 @@
+use crate::{MountProps, Result, Unit, UnitType};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Mount",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdMount {
+    #[dbus_proxy(property, name = "Where")]
+    fn where_(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn what(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn options(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "Type")]
+    fn ty(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "ControlPID")]
+    fn control_pid(&self) -> zbus::Result<u32>;
+}
+
+impl SystemdMountProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<MountProps> {
+        let mount_props = MountProps::builder()
+            .where_(self.where_()?)
+            .what(self.what()?)
+            .options(self.options()?)
+            .ty(self.ty()?)
+            .result(self.result()?)
+            .control_pid(self.control_pid()?)
+            .build();
+        Ok(mount_props)
+    }
+}
+
+impl SystemdMountProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<MountProps> {
+        let mount_props = MountProps::builder()
+            .where_(self.where_().await?)
+            .what(self.what().await?)
+            .options(self.options().await?)
+            .ty(self.ty().await?)
+            .result(self.result().await?)
+            .control_pid(self.control_pid().await?)
+            .build();
+        Ok(mount_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdMountProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdMountProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdMountProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdMountProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
+
+// proxy of a mount unit from the manager's unit listing
+pub async fn build_nonblock_proxy_from_unit(unit: &Unit) -> Result<SystemdMountProxy<'static>> {
+    unit.check_unit_type(UnitType::Mount)?;
+    build_nonblock_proxy(unit.object_path.clone()).await
+}
+
+pub fn build_blocking_proxy_from_unit(unit: &Unit) -> Result<SystemdMountProxyBlocking<'static>> {
+    unit.check_unit_type(UnitType::Mount)?;
+    build_blocking_proxy(unit.object_path.clone())
+}
@@ -0,0 +1,237 @@
+use crate::{escape_unit_name, unescape_unit_name, ErrorImpl, Result, Unit};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#Description
+// UNIT_NAME_MAX in systemd includes the trailing NUL
+const UNIT_NAME_MAX_LENGTH: usize = 255;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum UnitType {
+    Service,
+    Socket,
+    Target,
+    Device,
+    Mount,
+    Automount,
+    Swap,
+    Timer,
+    Path,
+    Slice,
+    Scope,
+}
+
+impl FromStr for UnitType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let ty = match s {
+            "service" => UnitType::Service,
+            "socket" => UnitType::Socket,
+            "target" => UnitType::Target,
+            "device" => UnitType::Device,
+            "mount" => UnitType::Mount,
+            "automount" => UnitType::Automount,
+            "swap" => UnitType::Swap,
+            "timer" => UnitType::Timer,
+            "path" => UnitType::Path,
+            "slice" => UnitType::Slice,
+            "scope" => UnitType::Scope,
+            _ => return Err(ErrorImpl::UnknownUnitType(s.to_owned()).into()),
+        };
+        Ok(ty)
+    }
+}
+
+impl Display for UnitType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ty = match self {
+            UnitType::Service => "service",
+            UnitType::Socket => "socket",
+            UnitType::Target => "target",
+            UnitType::Device => "device",
+            UnitType::Mount => "mount",
+            UnitType::Automount => "automount",
+            UnitType::Swap => "swap",
+            UnitType::Timer => "timer",
+            UnitType::Path => "path",
+            UnitType::Slice => "slice",
+            UnitType::Scope => "scope",
+        };
+        write!(f, "{}", ty)
+    }
+}
+
+// characters allowed in unit name prefixes and instances besides ascii alphanumerics
+fn is_valid_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, ':' | '-' | '_' | '.' | '\\')
+}
+
+fn invalid(name: &str, detail: &'static str) -> crate::Error {
+    ErrorImpl::InvalidUnitName(name.to_owned(), detail).into()
+}
+
+// a validated unit name, "prefix.type", "prefix@.type" (template) or "prefix@instance.type"
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnitName {
+    name: String,
+    prefix: String,
+    instance: Option<String>,
+    unit_type: UnitType,
+}
+
+impl UnitName {
+    pub fn as_str(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn prefix(&self) -> &str {
+        self.prefix.as_str()
+    }
+
+    // instance of an instantiated template, None for plain and template units
+    pub fn instance(&self) -> Option<&str> {
+        match self.instance.as_deref() {
+            Some("") | None => None,
+            Some(instance) => Some(instance),
+        }
+    }
+
+    pub fn unit_type(&self) -> UnitType {
+        self.unit_type
+    }
+
+    pub fn is_template(&self) -> bool {
+        matches!(self.instance.as_deref(), Some(""))
+    }
+
+    pub fn is_instance(&self) -> bool {
+        self.instance().is_some()
+    }
+
+    // "worker@.service" + "a b" => "worker@a\x20b.service"
+    pub fn instantiate(&self, instance: &str) -> Result<UnitName> {
+        if !self.is_template() {
+            return Err(invalid(self.as_str(), "not a template unit"));
+        }
+        // an empty instance would name the template itself
+        if instance.is_empty() {
+            return Err(invalid(self.as_str(), "empty instance"));
+        }
+        let escaped = escape_unit_name(instance);
+        format!("{}@{}.{}", self.prefix, escaped, self.unit_type).parse()
+    }
+
+    // "worker@a\x20b.service" => "worker@.service"
+    pub fn template(&self) -> Option<UnitName> {
+        self.instance()?;
+        Some(UnitName {
+            name: format!("{}@.{}", self.prefix, self.unit_type),
+            prefix: self.prefix.to_owned(),
+            instance: Some(String::new()),
+            unit_type: self.unit_type,
+        })
+    }
+
+    // "worker@a\x20b.service" => ("worker@.service", "a b")
+    pub fn split_instance(&self) -> Result<(UnitName, String)> {
+        let (template, instance) = match (self.template(), self.instance()) {
+            (Some(template), Some(instance)) => (template, instance),
+            _ => return Err(invalid(self.as_str(), "not an instance unit")),
+        };
+        let instance = unescape_unit_name(instance)?;
+        Ok((template, instance))
+    }
+}
+
+// group instantiated units by their template, units which are not template instances are skipped
+pub fn group_units_by_template<I>(units: I) -> HashMap<UnitName, Vec<Unit>>
+where
+    I: IntoIterator<Item = Unit>,
+{
+    let mut groups: HashMap<UnitName, Vec<Unit>> = HashMap::new();
+    for unit in units {
+        let template = match unit.unit_name().ok().and_then(|name| name.template()) {
+            Some(template) => template,
+            None => continue,
+        };
+        groups.entry(template).or_default().push(unit);
+    }
+    groups
+}
+
+impl FromStr for UnitName {
+    type Err = crate::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        if name.is_empty() {
+            return Err(invalid(name, "empty name"));
+        }
+        if name.len() > UNIT_NAME_MAX_LENGTH {
+            return Err(invalid(name, "name too long"));
+        }
+        let (stem, suffix) = match name.rsplit_once('.') {
+            Some(split) => split,
+            None => return Err(invalid(name, "missing unit type suffix")),
+        };
+        let unit_type: UnitType = suffix.parse()?;
+        let (prefix, instance) = match stem.split_once('@') {
+            Some((prefix, instance)) => (prefix, Some(instance)),
+            None => (stem, None),
+        };
+        if prefix.is_empty() {
+            return Err(invalid(name, "empty prefix"));
+        }
+        if !prefix.chars().all(is_valid_char) {
+            return Err(invalid(name, "invalid character in prefix"));
+        }
+        if let Some(instance) = instance {
+            if !instance.chars().all(|c| is_valid_char(c) || c == '@') {
+                return Err(invalid(name, "invalid character in instance"));
+            }
+        }
+        Ok(UnitName {
+            name: name.to_owned(),
+            prefix: prefix.to_owned(),
+            instance: instance.map(str::to_owned),
+            unit_type,
+        })
+    }
+}
+
+impl TryFrom<&str> for UnitName {
+    type Error = crate::Error;
+
+    fn try_from(name: &str) -> Result<Self> {
+        name.parse()
+    }
+}
+
+impl Display for UnitName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl AsRef<str> for UnitName {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+// unit names are sent over dbus as plain strings
+impl zvariant::Type for UnitName {
+    fn signature() -> zvariant::Signature<'static> {
+        String::signature()
+    }
+}
+
+impl zbus::export::serde::Serialize for UnitName {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: zbus::export::serde::Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
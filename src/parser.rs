@@ -0,0 +1,989 @@
+use crate::{
+    templates::is_variable_reference, Condition, ConditionType, EnvironmentFile,
+    EnvironmentVariable, Error, ErrorImpl, ExecConfigurationBuilder, InstallConfiguration,
+    InstallConfigurationBuilder, ResourceControlConfigurationBuilder, Result,
+    SecurityConfigurationBuilder, ServiceConfiguration, ServiceConfigurationBuilder,
+    ServiceUnitConfiguration, StandardOutput, UnitConfiguration, UnitConfigurationBuilder,
+};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+// unit file in the systemd ini dialect, keeps comments and layout so that it re-serializes losslessly
+// https://www.freedesktop.org/software/systemd/man/systemd.syntax.html
+pub struct UnitDocument {
+    // blank lines and comments before the first section
+    preamble: Vec<DocumentLine>,
+    sections: Vec<DocumentSection>,
+    trailing_newline: bool,
+}
+
+pub enum DocumentLine {
+    Blank(String),
+    Comment(String),
+    Directive(Directive),
+}
+
+impl DocumentLine {
+    fn is_blank(&self) -> bool {
+        matches!(self, DocumentLine::Blank(_))
+    }
+}
+
+impl Display for DocumentLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocumentLine::Blank(raw) => write!(f, "{}", raw),
+            DocumentLine::Comment(raw) => write!(f, "{}", raw),
+            DocumentLine::Directive(directive) => write!(f, "{}", directive),
+        }
+    }
+}
+
+// Key=Value assignment, possibly continued over several lines with a trailing backslash
+pub struct Directive {
+    key: String,
+    value: String,
+    line: Option<usize>,
+    // original text, dropped once the directive is modified
+    raw: Option<String>,
+}
+
+impl Directive {
+    pub fn new(key: &str, value: &str) -> Directive {
+        Directive {
+            key: key.to_owned(),
+            value: value.to_owned(),
+            line: None,
+            raw: None,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    // first line of the directive in the parsed file, None if added afterwards
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    // empty assignment, resets list settings and restores the default of others
+    pub fn is_reset(&self) -> bool {
+        self.value.is_empty()
+    }
+
+    pub fn set_value(&mut self, value: &str) {
+        self.value = value.to_owned();
+        self.raw = None;
+    }
+}
+
+impl Display for Directive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.raw {
+            Some(raw) => write!(f, "{}", raw),
+            None => write!(f, "{}={}", self.key, self.value),
+        }
+    }
+}
+
+pub struct DocumentSection {
+    name: String,
+    line: Option<usize>,
+    // file the section was read from, sections of several files meet in a merged document
+    origin: Option<PathBuf>,
+    raw: Option<String>,
+    lines: Vec<DocumentLine>,
+}
+
+impl DocumentSection {
+    pub fn new(name: &str) -> DocumentSection {
+        DocumentSection {
+            name: name.to_owned(),
+            line: None,
+            origin: None,
+            raw: None,
+            lines: vec![],
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    // line of the section header in the parsed file, None if added afterwards
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn origin(&self) -> Option<&Path> {
+        self.origin.as_deref()
+    }
+
+    pub fn lines(&self) -> &[DocumentLine] {
+        &self.lines
+    }
+
+    pub fn lines_mut(&mut self) -> &mut Vec<DocumentLine> {
+        &mut self.lines
+    }
+
+    pub fn directives(&self) -> impl Iterator<Item = &Directive> {
+        self.lines.iter().filter_map(|line| match line {
+            DocumentLine::Directive(directive) => Some(directive),
+            _ => None,
+        })
+    }
+
+    // value of the last assignment, None if unset or reset
+    pub fn get(&self, key: &str) -> Option<&str> {
+        effective_value(self.directives(), key)
+    }
+
+    // values assigned after the last reset
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        effective_values(self.directives(), key)
+    }
+
+    // replace all assignments of key with a single one, keeping the position of the first
+    pub fn set(&mut self, key: &str, value: &str) {
+        let mut found = false;
+        self.lines.retain_mut(|line| match line {
+            DocumentLine::Directive(directive) if directive.key == key => {
+                if found {
+                    return false;
+                }
+                found = true;
+                directive.set_value(value);
+                true
+            }
+            _ => true,
+        });
+        if !found {
+            self.append(key, value);
+        }
+    }
+
+    // add another assignment after the last non blank line
+    pub fn append(&mut self, key: &str, value: &str) {
+        let position = self
+            .lines
+            .iter()
+            .rposition(|line| !line.is_blank())
+            .map_or(0, |position| position + 1);
+        self.lines.insert(
+            position,
+            DocumentLine::Directive(Directive::new(key, value)),
+        );
+    }
+
+    // remove all assignments of key, returns whether any existed
+    pub fn remove(&mut self, key: &str) -> bool {
+        let len = self.lines.len();
+        self.lines.retain(|line| match line {
+            DocumentLine::Directive(directive) => directive.key != key,
+            _ => true,
+        });
+        len != self.lines.len()
+    }
+}
+
+impl Display for DocumentSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.raw {
+            Some(raw) => write!(f, "{}", raw)?,
+            None => write!(f, "[{}]", self.name)?,
+        }
+        for line in self.lines.iter() {
+            write!(f, "\n{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+fn effective_value<'a>(
+    directives: impl Iterator<Item = &'a Directive>,
+    key: &str,
+) -> Option<&'a str> {
+    directives
+        .filter(|directive| directive.key == key)
+        .last()
+        .filter(|directive| !directive.is_reset())
+        .map(|directive| directive.value())
+}
+
+fn effective_values<'a>(
+    directives: impl Iterator<Item = &'a Directive>,
+    key: &str,
+) -> Vec<&'a str> {
+    let mut values = vec![];
+    for directive in directives.filter(|directive| directive.key == key) {
+        match directive.is_reset() {
+            true => values.clear(),
+            false => values.push(directive.value()),
+        }
+    }
+    values
+}
+
+impl UnitDocument {
+    pub fn new() -> UnitDocument {
+        UnitDocument {
+            preamble: vec![],
+            sections: vec![],
+            trailing_newline: true,
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<UnitDocument> {
+        s.parse()
+    }
+
+    pub fn preamble(&self) -> &[DocumentLine] {
+        &self.preamble
+    }
+
+    pub fn sections(&self) -> &[DocumentSection] {
+        &self.sections
+    }
+
+    pub fn sections_mut(&mut self) -> &mut Vec<DocumentSection> {
+        &mut self.sections
+    }
+
+    // first section with the given name
+    pub fn section(&self, name: &str) -> Option<&DocumentSection> {
+        self.sections.iter().find(|section| section.name == name)
+    }
+
+    pub fn section_mut(&mut self, name: &str) -> Option<&mut DocumentSection> {
+        self.sections
+            .iter_mut()
+            .find(|section| section.name == name)
+    }
+
+    // first section with the given name, appended to the document if missing
+    pub fn section_or_insert(&mut self, name: &str) -> &mut DocumentSection {
+        let index = match self
+            .sections
+            .iter()
+            .position(|section| section.name == name)
+        {
+            Some(index) => index,
+            None => {
+                self.push_section(DocumentSection::new(name));
+                self.sections.len() - 1
+            }
+        };
+        &mut self.sections[index]
+    }
+
+    // remove all sections with the given name, returns whether any existed
+    pub fn remove_section(&mut self, name: &str) -> bool {
+        let len = self.sections.len();
+        self.sections.retain(|section| section.name != name);
+        len != self.sections.len()
+    }
+
+    // repeated sections are merged, so look through all of them
+    fn directives(&self, section: &str) -> Vec<&Directive> {
+        self.sections
+            .iter()
+            .filter(|s| s.name == section)
+            .flat_map(|s| s.directives())
+            .collect()
+    }
+
+    // file of the assignment that is in effect, to answer why a setting is active
+    pub fn origin(&self, section: &str, key: &str) -> Option<&Path> {
+        self.sections
+            .iter()
+            .rfind(|s| s.name == section && s.directives().any(|directive| directive.key == key))
+            .and_then(DocumentSection::origin)
+    }
+
+    pub fn set_origin(&mut self, origin: &Path) {
+        for section in self.sections.iter_mut() {
+            section.origin = Some(origin.to_owned());
+        }
+    }
+
+    // append the sections of another document, e.g. a drop-in, which then take precedence
+    pub fn merge(&mut self, other: UnitDocument) {
+        for section in other.sections {
+            self.push_section(section);
+        }
+    }
+
+    // keep an empty line between sections
+    fn push_section(&mut self, section: DocumentSection) {
+        if let Some(last) = self.sections.last_mut() {
+            if !last.lines.last().is_some_and(DocumentLine::is_blank) {
+                last.lines.push(DocumentLine::Blank(String::new()));
+            }
+        }
+        self.sections.push(section);
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        effective_value(self.directives(section).into_iter(), key)
+    }
+
+    pub fn get_all(&self, section: &str, key: &str) -> Vec<&str> {
+        effective_values(self.directives(section).into_iter(), key)
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        self.section_or_insert(section).set(key, value)
+    }
+
+    pub fn append(&mut self, section: &str, key: &str, value: &str) {
+        self.section_or_insert(section).append(key, value)
+    }
+
+    pub fn remove(&mut self, section: &str, key: &str) -> bool {
+        let mut removed = false;
+        for s in self.sections.iter_mut().filter(|s| s.name == section) {
+            removed |= s.remove(key);
+        }
+        removed
+    }
+
+    fn push_line(&mut self, line: DocumentLine) {
+        match self.sections.last_mut() {
+            Some(section) => section.lines.push(line),
+            None => self.preamble.push(line),
+        }
+    }
+}
+
+impl Default for UnitDocument {
+    fn default() -> Self {
+        UnitDocument::new()
+    }
+}
+
+fn is_comment(text: &str) -> bool {
+    text.starts_with('#') || text.starts_with(';')
+}
+
+// text before a trailing backslash that is not itself escaped
+fn strip_continuation(text: &str) -> Option<&str> {
+    let backslashes = text.chars().rev().take_while(|c| *c == '\\').count();
+    match backslashes % 2 {
+        1 => Some(&text[..text.len() - 1]),
+        _ => None,
+    }
+}
+
+impl FromStr for UnitDocument {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut lines: Vec<&str> = s.split('\n').collect();
+        let trailing_newline = lines.last() == Some(&"");
+        if trailing_newline {
+            lines.pop();
+        }
+        let mut document = UnitDocument {
+            trailing_newline,
+            ..Default::default()
+        };
+        let mut index = 0;
+        while index < lines.len() {
+            let number = index + 1;
+            let raw = lines[index];
+            let text = raw.trim();
+            index += 1;
+            if text.is_empty() {
+                document.push_line(DocumentLine::Blank(raw.to_owned()));
+                continue;
+            }
+            if is_comment(text) {
+                document.push_line(DocumentLine::Comment(raw.to_owned()));
+                continue;
+            }
+            if let Some(header) = text.strip_prefix('[') {
+                let name = header.strip_suffix(']').ok_or_else(|| {
+                    ErrorImpl::Parse(number, "unterminated section header".into())
+                })?;
+                if name.is_empty() {
+                    return Err(ErrorImpl::Parse(number, "empty section name".into()).into());
+                }
+                document.sections.push(DocumentSection {
+                    name: name.to_owned(),
+                    line: Some(number),
+                    origin: None,
+                    raw: Some(raw.to_owned()),
+                    lines: vec![],
+                });
+                continue;
+            }
+            // a trailing backslash joins the next line with a space, comments in between are skipped
+            let mut raw_lines = vec![raw];
+            let mut logical = String::new();
+            let mut current = text;
+            while let Some(head) = strip_continuation(current) {
+                logical.push_str(head);
+                logical.push(' ');
+                current = "";
+                while let Some(next) = lines.get(index) {
+                    raw_lines.push(next);
+                    index += 1;
+                    if !is_comment(next.trim()) {
+                        current = next.trim();
+                        break;
+                    }
+                }
+            }
+            logical.push_str(current);
+            let (key, value) = logical
+                .split_once('=')
+                .ok_or_else(|| ErrorImpl::Parse(number, "missing '=' in assignment".into()))?;
+            let key = key.trim();
+            if key.is_empty() {
+                return Err(ErrorImpl::Parse(number, "empty key in assignment".into()).into());
+            }
+            if document.sections.is_empty() {
+                return Err(
+                    ErrorImpl::Parse(number, "assignment outside of section".into()).into(),
+                );
+            }
+            document.push_line(DocumentLine::Directive(Directive {
+                key: key.to_owned(),
+                value: value.trim().to_owned(),
+                line: Some(number),
+                raw: Some(raw_lines.join("\n")),
+            }));
+        }
+        Ok(document)
+    }
+}
+
+impl Display for UnitDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut chunks: Vec<String> = self.preamble.iter().map(ToString::to_string).collect();
+        chunks.extend(self.sections.iter().map(ToString::to_string));
+        if chunks.is_empty() {
+            return Ok(());
+        }
+        write!(f, "{}", chunks.join("\n"))?;
+        if self.trailing_newline {
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+// conversion into the typed templates, which borrow from the document, so values the
+// templates would render differently (specifiers, quoting, escapes) are rejected, as are
+// keys and value forms the templates have no field for, e.g. DeviceAllow= or StandardOutput=fd:
+
+fn unsupported(directive: &Directive, detail: &str) -> Error {
+    ErrorImpl::Parse(
+        directive.line.unwrap_or_default(),
+        format!("{}={}: {}", directive.key, directive.value, detail),
+    )
+    .into()
+}
+
+fn parse_value<T: FromStr<Err = Error>>(directive: &Directive) -> Result<Option<T>> {
+    if directive.is_reset() {
+        return Ok(None);
+    }
+    let value = directive
+        .value
+        .parse()
+        .map_err(|err: Error| unsupported(directive, &err.to_string()))?;
+    Ok(Some(value))
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.syntax.html#Boolean
+pub(crate) fn parse_boolean(value: &str) -> Option<bool> {
+    match value.to_ascii_lowercase().as_str() {
+        "1" | "yes" | "y" | "true" | "t" | "on" => Some(true),
+        "0" | "no" | "n" | "false" | "f" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+fn parse_bool(directive: &Directive) -> Result<Option<bool>> {
+    if directive.is_reset() {
+        return Ok(None);
+    }
+    match parse_boolean(&directive.value) {
+        Some(value) => Ok(Some(value)),
+        None => Err(unsupported(directive, "invalid boolean")),
+    }
+}
+
+fn parse_number<T: FromStr>(directive: &Directive) -> Result<Option<T>> {
+    if directive.is_reset() {
+        return Ok(None);
+    }
+    match directive.value.parse() {
+        Ok(value) => Ok(Some(value)),
+        Err(_) => Err(unsupported(directive, "invalid number")),
+    }
+}
+
+fn parse_mode(directive: &Directive) -> Result<Option<u32>> {
+    if directive.is_reset() {
+        return Ok(None);
+    }
+    match u32::from_str_radix(&directive.value, 8) {
+        Ok(mode) => Ok(Some(mode)),
+        Err(_) => Err(unsupported(directive, "invalid octal mode")),
+    }
+}
+
+fn optional(directive: &Directive) -> Option<&str> {
+    match directive.is_reset() {
+        true => None,
+        false => Some(directive.value()),
+    }
+}
+
+fn extend_list<'a>(list: &mut Vec<&'a str>, directive: &'a Directive) {
+    match directive.is_reset() {
+        true => list.clear(),
+        false => list.extend(directive.value.split_whitespace()),
+    }
+}
+
+// a leading "~" inverts the whole line, so such a line is kept as a single entry
+fn entries(directive: &Directive) -> Vec<&str> {
+    match directive.value.starts_with('~') {
+        true => vec![directive.value()],
+        false => directive.value.split_whitespace().collect(),
+    }
+}
+
+fn extend_entries<'a, T: From<&'a str>>(list: &mut Vec<T>, directive: &'a Directive) {
+    match directive.is_reset() {
+        true => list.clear(),
+        false => list.extend(entries(directive).into_iter().map(T::from)),
+    }
+}
+
+fn pair(directive: &Directive, separator: char) -> Result<(&str, &str)> {
+    directive
+        .value
+        .split_once(separator)
+        .map(|(first, second)| (first.trim(), second.trim()))
+        .filter(|(first, second)| !first.is_empty() && !second.is_empty())
+        .ok_or_else(|| {
+            unsupported(
+                directive,
+                &format!("expected two values separated by {:?}", separator),
+            )
+        })
+}
+
+fn extend_pairs<'a>(
+    pairs: &mut Vec<(&'a str, &'a str)>,
+    directive: &'a Directive,
+    separator: char,
+) -> Result<()> {
+    match directive.is_reset() {
+        true => pairs.clear(),
+        false => pairs.push(pair(directive, separator)?),
+    }
+    Ok(())
+}
+
+fn extend_device_values<'a, T: FromStr>(
+    values: &mut Vec<(&'a str, T)>,
+    directive: &'a Directive,
+) -> Result<()> {
+    if directive.is_reset() {
+        values.clear();
+        return Ok(());
+    }
+    let (device, value) = pair(directive, ' ')?;
+    let value = value
+        .parse()
+        .map_err(|_| unsupported(directive, "invalid device value"))?;
+    values.push((device, value));
+    Ok(())
+}
+
+fn standard_output(directive: &Directive) -> Result<Option<StandardOutput<'_>>> {
+    let output = match directive.value() {
+        "" => return Ok(None),
+        "inherit" => StandardOutput::Inherit,
+        "null" => StandardOutput::Null,
+        "tty" => StandardOutput::Tty,
+        "journal" => StandardOutput::Journal,
+        "kmsg" => StandardOutput::Kmsg,
+        "journal+console" => StandardOutput::JournalAndConsole,
+        "kmsg+console" => StandardOutput::KmsgAndConsole,
+        "socket" => StandardOutput::Socket,
+        value => match value.split_once(':') {
+            Some(("file", path)) => StandardOutput::File(path),
+            Some(("append", path)) => StandardOutput::Append(path),
+            Some(("truncate", path)) => StandardOutput::Truncate(path),
+            _ => return Err(unsupported(directive, "unsupported output target")),
+        },
+    };
+    Ok(Some(output))
+}
+
+fn extend_environment_files<'a>(files: &mut Vec<EnvironmentFile<'a>>, directive: &'a Directive) {
+    if directive.is_reset() {
+        files.clear();
+        return;
+    }
+    let file = match directive.value.strip_prefix('-') {
+        Some(path) => EnvironmentFile {
+            path,
+            optional: true,
+        },
+        None => EnvironmentFile {
+            path: directive.value(),
+            optional: false,
+        },
+    };
+    files.push(file);
+}
+
+// an empty assignment resets all conditions, or all asserts
+fn extend_conditions<'a>(
+    conditions: &mut Vec<Condition<'a>>,
+    ty: &str,
+    directive: &'a Directive,
+) -> Result<()> {
+    if directive.is_reset() {
+        conditions.clear();
+        return Ok(());
+    }
+    let ty: ConditionType = ty
+        .parse()
+        .map_err(|_| unsupported(directive, "unsupported condition"))?;
+    let mut builder = Condition::builder().ty(ty);
+    let mut value = directive.value();
+    if let Some(rest) = value.strip_prefix('|') {
+        builder = builder.trigger();
+        value = rest;
+    }
+    if let Some(rest) = value.strip_prefix('!') {
+        builder = builder.negate();
+        value = rest;
+    }
+    conditions.push(builder.value(value).build());
+    Ok(())
+}
+
+fn command(directive: &Directive) -> Result<Vec<&str>> {
+    let words: Vec<&str> = directive.value.split_whitespace().collect();
+    for word in words.iter() {
+        if *word == ";" {
+            return Err(unsupported(directive, "multiple commands per line"));
+        }
+        if word.contains(['"', '\'', '\\', '%']) {
+            return Err(unsupported(
+                directive,
+                "quoted, escaped or specifier argument",
+            ));
+        }
+        if word.contains('$') && !is_variable_reference(word) {
+            return Err(unsupported(
+                directive,
+                "variable reference other than ${NAME}",
+            ));
+        }
+    }
+    Ok(words)
+}
+
+fn extend_commands<'a>(commands: &mut Vec<Vec<&'a str>>, directive: &'a Directive) -> Result<()> {
+    match directive.is_reset() {
+        true => commands.clear(),
+        false => commands.push(command(directive)?),
+    }
+    Ok(())
+}
+
+fn extend_environment<'a>(
+    envs: &mut Vec<EnvironmentVariable<'a>>,
+    directive: &'a Directive,
+) -> Result<()> {
+    if directive.is_reset() {
+        envs.clear();
+        return Ok(());
+    }
+    let mut rest = directive.value();
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return Ok(());
+        }
+        let (word, tail) = match rest.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted
+                    .find('"')
+                    .ok_or_else(|| unsupported(directive, "unterminated quote"))?;
+                (&quoted[..end], &quoted[end + 1..])
+            }
+            None => {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+        if word.contains(['"', '\'', '\\', '%']) {
+            return Err(unsupported(directive, "escaped or specifier value"));
+        }
+        let (key, value) = word
+            .split_once('=')
+            .ok_or_else(|| unsupported(directive, "missing '=' in variable"))?;
+        envs.push(EnvironmentVariable { key, value });
+        rest = tail;
+    }
+}
+
+fn apply_unit<'a>(unit: &mut UnitConfigurationBuilder<'a>, directive: &'a Directive) -> Result<()> {
+    match directive.key() {
+        "Description" => {
+            if directive.value.contains('%') {
+                return Err(unsupported(directive, "specifier in description"));
+            }
+            unit.description = directive.value();
+        }
+        "Documentation" => extend_list(&mut unit.documentation, directive),
+        "Requires" => extend_list(&mut unit.requires, directive),
+        "Wants" => extend_list(&mut unit.wants, directive),
+        "Requisite" => extend_list(&mut unit.requisite, directive),
+        "BindsTo" => extend_list(&mut unit.binds_to, directive),
+        "PartOf" => extend_list(&mut unit.part_of, directive),
+        "Upholds" => extend_list(&mut unit.upholds, directive),
+        "Conflicts" => extend_list(&mut unit.conflicts, directive),
+        "Before" => extend_list(&mut unit.before, directive),
+        "After" => extend_list(&mut unit.after, directive),
+        "OnFailure" => extend_list(&mut unit.on_failure, directive),
+        "OnSuccess" => extend_list(&mut unit.on_success, directive),
+        "StartLimitIntervalSec" => unit.start_limit_interval_sec = optional(directive),
+        "StartLimitBurst" => unit.start_limit_burst = parse_number(directive)?,
+        "JobTimeoutSec" => unit.job_timeout_sec = optional(directive),
+        "DefaultDependencies" => unit.default_dependencies = parse_bool(directive)?,
+        "StopWhenUnneeded" => unit.stop_when_unneeded = parse_bool(directive)?,
+        "RefuseManualStart" => unit.refuse_manual_start = parse_bool(directive)?,
+        "RefuseManualStop" => unit.refuse_manual_stop = parse_bool(directive)?,
+        "AllowIsolate" => unit.allow_isolate = parse_bool(directive)?,
+        "IgnoreOnIsolate" => unit.ignore_on_isolate = parse_bool(directive)?,
+        key => match (key.strip_prefix("Condition"), key.strip_prefix("Assert")) {
+            (Some(ty), _) => extend_conditions(&mut unit.conditions, ty, directive)?,
+            (_, Some(ty)) => extend_conditions(&mut unit.asserts, ty, directive)?,
+            _ => return Err(unsupported(directive, "unsupported key in [Unit]")),
+        },
+    }
+    Ok(())
+}
+
+fn apply_service<'a>(
+    service: &mut ServiceConfigurationBuilder<'a>,
+    directive: &'a Directive,
+) -> Result<()> {
+    match directive.key() {
+        "Type" => service.ty = parse_value(directive)?,
+        "ExecStart" => {
+            if directive.is_reset() {
+                service.exec_start.clear();
+            } else if !service.exec_start.is_empty() {
+                return Err(unsupported(directive, "multiple ExecStart= commands"));
+            } else {
+                service.exec_start = command(directive)?;
+            }
+        }
+        "Restart" => service.restart_policy = parse_value(directive)?,
+        "RestartSec" => service.restart_sec = optional(directive),
+        "WorkingDirectory" => service.working_directory = optional(directive),
+        "User" => service.user = optional(directive),
+        "Group" => service.group = optional(directive),
+        "Environment" => extend_environment(&mut service.envs, directive)?,
+        "PIDFile" => service.pid_file = optional(directive),
+        "RemainAfterExit" => service.remain_after_exit = parse_bool(directive)?,
+        "ExecStartPre" => extend_commands(&mut service.exec_start_pre, directive)?,
+        "ExecStartPost" => extend_commands(&mut service.exec_start_post, directive)?,
+        "ExecReload" => extend_commands(&mut service.exec_reload, directive)?,
+        "ExecStop" => extend_commands(&mut service.exec_stop, directive)?,
+        "ExecStopPost" => extend_commands(&mut service.exec_stop_post, directive)?,
+        "TimeoutStartSec" => service.timeout_start_sec = optional(directive),
+        "TimeoutStopSec" => service.timeout_stop_sec = optional(directive),
+        "SuccessExitStatus" => extend_list(&mut service.success_exit_status, directive),
+        "KillMode" => service.kill_mode = parse_value(directive)?,
+        "KillSignal" => service.kill_signal = optional(directive),
+        "NotifyAccess" => service.notify_access = parse_value(directive)?,
+        _ => {
+            let known = apply_exec(&mut service.exec, directive)?
+                || apply_security(&mut service.security, directive)?
+                || apply_resource_control(&mut service.resource_control, directive)?;
+            if !known {
+                return Err(unsupported(directive, "unsupported key in [Service]"));
+            }
+        }
+    }
+    Ok(())
+}
+
+// the apply_* functions of the sub-configurations return false for keys they do not know
+
+fn apply_exec<'a>(
+    exec: &mut ExecConfigurationBuilder<'a>,
+    directive: &'a Directive,
+) -> Result<bool> {
+    match directive.key() {
+        "LimitNOFILE" => exec.limit_nofile = parse_value(directive)?,
+        "LimitNPROC" => exec.limit_nproc = parse_value(directive)?,
+        "LimitCORE" => exec.limit_core = parse_value(directive)?,
+        "Nice" => exec.nice = parse_number(directive)?,
+        "OOMScoreAdjust" => exec.oom_score_adjust = parse_number(directive)?,
+        "UMask" => exec.umask = parse_mode(directive)?,
+        "EnvironmentFile" => extend_environment_files(&mut exec.environment_files, directive),
+        "StandardOutput" => exec.standard_output = standard_output(directive)?,
+        "StandardError" => exec.standard_error = standard_output(directive)?,
+        "SyslogIdentifier" => exec.syslog_identifier = optional(directive),
+        "LoadCredential" => extend_pairs(&mut exec.load_credentials, directive, ':')?,
+        "SetCredential" => {
+            if directive.value.contains(['"', '\\', '%']) {
+                return Err(unsupported(directive, "escaped or specifier value"));
+            }
+            extend_pairs(&mut exec.set_credentials, directive, ':')?
+        }
+        "StateDirectory" => extend_list(&mut exec.state_directory, directive),
+        "StateDirectoryMode" => exec.state_directory_mode = parse_mode(directive)?,
+        "RuntimeDirectory" => extend_list(&mut exec.runtime_directory, directive),
+        "RuntimeDirectoryMode" => exec.runtime_directory_mode = parse_mode(directive)?,
+        "LogsDirectory" => extend_list(&mut exec.logs_directory, directive),
+        "LogsDirectoryMode" => exec.logs_directory_mode = parse_mode(directive)?,
+        "CacheDirectory" => extend_list(&mut exec.cache_directory, directive),
+        "CacheDirectoryMode" => exec.cache_directory_mode = parse_mode(directive)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn apply_security<'a>(
+    security: &mut SecurityConfigurationBuilder<'a>,
+    directive: &'a Directive,
+) -> Result<bool> {
+    match directive.key() {
+        "ProtectSystem" => security.protect_system = parse_value(directive)?,
+        "ProtectHome" => security.protect_home = parse_value(directive)?,
+        "PrivateTmp" => security.private_tmp = parse_bool(directive)?,
+        "PrivateDevices" => security.private_devices = parse_bool(directive)?,
+        "PrivateNetwork" => security.private_network = parse_bool(directive)?,
+        "NoNewPrivileges" => security.no_new_privileges = parse_bool(directive)?,
+        "DynamicUser" => security.dynamic_user = parse_bool(directive)?,
+        "CapabilityBoundingSet" => extend_entries(&mut security.capability_bounding_set, directive),
+        "AmbientCapabilities" => extend_list(&mut security.ambient_capabilities, directive),
+        "ReadOnlyPaths" => extend_list(&mut security.read_only_paths, directive),
+        "ReadWritePaths" => extend_list(&mut security.read_write_paths, directive),
+        "InaccessiblePaths" => extend_list(&mut security.inaccessible_paths, directive),
+        "RestrictAddressFamilies" => {
+            extend_entries(&mut security.restrict_address_families, directive)
+        }
+        "SystemCallFilter" => extend_entries(&mut security.system_call_filter, directive),
+        "ProtectKernelTunables" => security.protect_kernel_tunables = parse_bool(directive)?,
+        "ProtectKernelModules" => security.protect_kernel_modules = parse_bool(directive)?,
+        "ProtectKernelLogs" => security.protect_kernel_logs = parse_bool(directive)?,
+        "MemoryDenyWriteExecute" => security.memory_deny_write_execute = parse_bool(directive)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn apply_resource_control<'a>(
+    resource_control: &mut ResourceControlConfigurationBuilder<'a>,
+    directive: &'a Directive,
+) -> Result<bool> {
+    match directive.key() {
+        "Slice" => resource_control.slice = optional(directive),
+        "CPUWeight" => resource_control.cpu_weight = parse_number(directive)?,
+        "CPUQuota" => {
+            resource_control.cpu_quota = match directive.value.strip_suffix('%') {
+                Some(percent) => Some(
+                    percent
+                        .parse()
+                        .map_err(|_| unsupported(directive, "invalid percentage"))?,
+                ),
+                None if directive.is_reset() => None,
+                None => return Err(unsupported(directive, "expected a percentage")),
+            }
+        }
+        "AllowedCPUs" => resource_control.allowed_cpus = optional(directive),
+        "MemoryMin" => resource_control.memory_min = parse_value(directive)?,
+        "MemoryLow" => resource_control.memory_low = parse_value(directive)?,
+        "MemoryHigh" => resource_control.memory_high = parse_value(directive)?,
+        "MemoryMax" => resource_control.memory_max = parse_value(directive)?,
+        "MemorySwapMax" => resource_control.memory_swap_max = parse_value(directive)?,
+        "TasksMax" => resource_control.tasks_max = parse_value(directive)?,
+        "IOWeight" => resource_control.io_weight = parse_number(directive)?,
+        "IODeviceWeight" => {
+            extend_device_values(&mut resource_control.io_device_weight, directive)?
+        }
+        "IOReadBandwidthMax" => {
+            extend_device_values(&mut resource_control.io_read_bandwidth_max, directive)?
+        }
+        "IOWriteBandwidthMax" => {
+            extend_device_values(&mut resource_control.io_write_bandwidth_max, directive)?
+        }
+        "IPAddressAllow" => extend_list(&mut resource_control.ip_address_allow, directive),
+        "IPAddressDeny" => extend_list(&mut resource_control.ip_address_deny, directive),
+        "Delegate" => resource_control.delegate = parse_bool(directive)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+fn apply_install<'a>(
+    install: &mut InstallConfigurationBuilder<'a>,
+    directive: &'a Directive,
+) -> Result<()> {
+    match directive.key() {
+        "WantedBy" => extend_list(&mut install.wanted_by, directive),
+        "RequiredBy" => extend_list(&mut install.required_by, directive),
+        "UpheldBy" => extend_list(&mut install.upheld_by, directive),
+        "Alias" => extend_list(&mut install.alias, directive),
+        "Also" => extend_list(&mut install.also, directive),
+        "DefaultInstance" => install.default_instance = optional(directive),
+        _ => return Err(unsupported(directive, "unsupported key in [Install]")),
+    }
+    Ok(())
+}
+
+impl<'a> TryFrom<&'a UnitDocument> for ServiceUnitConfiguration<'a> {
+    type Error = crate::Error;
+
+    fn try_from(document: &'a UnitDocument) -> Result<Self> {
+        let mut unit = UnitConfiguration::builder();
+        let mut service = ServiceConfiguration::builder();
+        // only what the document says, no implicit WantedBy=
+        let mut install = InstallConfiguration::builder().clear_default_wanted_by();
+        for section in document.sections() {
+            if !matches!(section.name(), "Unit" | "Service" | "Install") {
+                return Err(ErrorImpl::Parse(
+                    section.line.unwrap_or_default(),
+                    format!("unsupported section [{}] in service unit", section.name()),
+                )
+                .into());
+            }
+            for directive in section.directives() {
+                match section.name() {
+                    "Unit" => apply_unit(&mut unit, directive)?,
+                    "Service" => apply_service(&mut service, directive)?,
+                    _ => apply_install(&mut install, directive)?,
+                }
+            }
+        }
+        Ok(ServiceUnitConfiguration::builder()
+            .unit(unit)
+            .service(service)
+            .install(install)
+            .build())
+    }
+}
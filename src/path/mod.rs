@@ -0,0 +1,3 @@
+mod proxy;
+
+pub use proxy::*;
@@ -0,0 +1,61 @@
+use crate::{PathProps, PathSpecTuple, Result};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Path",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdPath {
+    #[dbus_proxy(property)]
+    fn unit(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn paths(&self) -> zbus::Result<Vec<PathSpecTuple>>;
+    #[dbus_proxy(property)]
+    fn make_directory(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+}
+
+impl SystemdPathProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<PathProps> {
+        let path_props = PathProps::builder()
+            .unit(self.unit()?)
+            .paths(self.paths()?)
+            .make_directory(self.make_directory()?)
+            .result(self.result()?)
+            .build();
+        Ok(path_props)
+    }
+}
+
+impl SystemdPathProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<PathProps> {
+        let path_props = PathProps::builder()
+            .unit(self.unit().await?)
+            .paths(self.paths().await?)
+            .make_directory(self.make_directory().await?)
+            .result(self.result().await?)
+            .build();
+        Ok(path_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdPathProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdPathProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdPathProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdPathProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
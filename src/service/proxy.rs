@@ -0,0 +1,109 @@
+use crate::{ExecCommandTuple, Result, ServiceProps};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Service",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdService {
+    #[dbus_proxy(property, name = "MainPID")]
+    fn main_pid(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property, name = "ControlPID")]
+    fn control_pid(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn exec_main_status(&self) -> zbus::Result<i32>;
+    #[dbus_proxy(property)]
+    fn exec_main_code(&self) -> zbus::Result<i32>;
+    #[dbus_proxy(property)]
+    fn exec_main_start_timestamp(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "NRestarts")]
+    fn n_restarts(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "Type")]
+    fn ty(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn restart(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn status_text(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn status_errno(&self) -> zbus::Result<i32>;
+    #[dbus_proxy(property, name = "WatchdogUSec")]
+    fn watchdog_usec(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn memory_current(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "CPUUsageNSec")]
+    fn cpu_usage_nsec(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn tasks_current(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn exec_start(&self) -> zbus::Result<Vec<ExecCommandTuple>>;
+}
+
+impl SystemdServiceProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<ServiceProps> {
+        let service_props = ServiceProps::builder()
+            .main_pid(self.main_pid()?)
+            .control_pid(self.control_pid()?)
+            .exec_main_status(self.exec_main_status()?)
+            .exec_main_code(self.exec_main_code()?)
+            .exec_main_start_timestamp(self.exec_main_start_timestamp()?)
+            .n_restarts(self.n_restarts()?)
+            .result(self.result()?)
+            .ty(self.ty()?)
+            .restart(self.restart()?)
+            .status_text(self.status_text()?)
+            .status_errno(self.status_errno()?)
+            .watchdog_usec(self.watchdog_usec()?)
+            .memory_current(self.memory_current()?)
+            .cpu_usage_nsec(self.cpu_usage_nsec()?)
+            .tasks_current(self.tasks_current()?)
+            .exec_start(self.exec_start()?)
+            .build();
+        Ok(service_props)
+    }
+}
+
+impl SystemdServiceProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<ServiceProps> {
+        let service_props = ServiceProps::builder()
+            .main_pid(self.main_pid().await?)
+            .control_pid(self.control_pid().await?)
+            .exec_main_status(self.exec_main_status().await?)
+            .exec_main_code(self.exec_main_code().await?)
+            .exec_main_start_timestamp(self.exec_main_start_timestamp().await?)
+            .n_restarts(self.n_restarts().await?)
+            .result(self.result().await?)
+            .ty(self.ty().await?)
+            .restart(self.restart().await?)
+            .status_text(self.status_text().await?)
+            .status_errno(self.status_errno().await?)
+            .watchdog_usec(self.watchdog_usec().await?)
+            .memory_current(self.memory_current().await?)
+            .cpu_usage_nsec(self.cpu_usage_nsec().await?)
+            .tasks_current(self.tasks_current().await?)
+            .exec_start(self.exec_start().await?)
+            .build();
+        Ok(service_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdServiceProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdServiceProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdServiceProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdServiceProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
@@ -0,0 +1,81 @@
+use crate::{Result, SliceProps};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Slice",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdSlice {
+    #[dbus_proxy(property)]
+    fn control_group(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn memory_current(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn memory_peak(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "CPUUsageNSec")]
+    fn cpu_usage_nsec(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn tasks_current(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IOReadBytes")]
+    fn io_read_bytes(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IOWriteBytes")]
+    fn io_write_bytes(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IPIngressBytes")]
+    fn ip_ingress_bytes(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "IPEgressBytes")]
+    fn ip_egress_bytes(&self) -> zbus::Result<u64>;
+}
+
+impl SystemdSliceProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<SliceProps> {
+        let slice_props = SliceProps::builder()
+            .control_group(self.control_group()?)
+            .memory_current(self.memory_current()?)
+            .memory_peak(self.memory_peak()?)
+            .cpu_usage_nsec(self.cpu_usage_nsec()?)
+            .tasks_current(self.tasks_current()?)
+            .io_read_bytes(self.io_read_bytes()?)
+            .io_write_bytes(self.io_write_bytes()?)
+            .ip_ingress_bytes(self.ip_ingress_bytes()?)
+            .ip_egress_bytes(self.ip_egress_bytes()?)
+            .build();
+        Ok(slice_props)
+    }
+}
+
+impl SystemdSliceProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<SliceProps> {
+        let slice_props = SliceProps::builder()
+            .control_group(self.control_group().await?)
+            .memory_current(self.memory_current().await?)
+            .memory_peak(self.memory_peak().await?)
+            .cpu_usage_nsec(self.cpu_usage_nsec().await?)
+            .tasks_current(self.tasks_current().await?)
+            .io_read_bytes(self.io_read_bytes().await?)
+            .io_write_bytes(self.io_write_bytes().await?)
+            .ip_ingress_bytes(self.ip_ingress_bytes().await?)
+            .ip_egress_bytes(self.ip_egress_bytes().await?)
+            .build();
+        Ok(slice_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdSliceProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdSliceProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdSliceProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdSliceProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
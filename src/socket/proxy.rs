@@ -0,0 +1,97 @@
+use crate::{Result, SocketListenTuple, SocketProps};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Socket",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdSocket {
+    #[dbus_proxy(property)]
+    fn listen(&self) -> zbus::Result<Vec<SocketListenTuple>>;
+    #[dbus_proxy(property)]
+    fn accept(&self) -> zbus::Result<bool>;
+    #[dbus_proxy(property, name = "NConnections")]
+    fn n_connections(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property, name = "NAccepted")]
+    fn n_accepted(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property, name = "NRefused")]
+    fn n_refused(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "ControlPID")]
+    fn control_pid(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn backlog(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn max_connections(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn socket_mode(&self) -> zbus::Result<u32>;
+    #[dbus_proxy(property)]
+    fn socket_user(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn socket_group(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn file_descriptor_name(&self) -> zbus::Result<String>;
+}
+
+impl SystemdSocketProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<SocketProps> {
+        let socket_props = SocketProps::builder()
+            .listen(self.listen()?)
+            .accept(self.accept()?)
+            .n_connections(self.n_connections()?)
+            .n_accepted(self.n_accepted()?)
+            .n_refused(self.n_refused()?)
+            .result(self.result()?)
+            .control_pid(self.control_pid()?)
+            .backlog(self.backlog()?)
+            .max_connections(self.max_connections()?)
+            .socket_mode(self.socket_mode()?)
+            .socket_user(self.socket_user()?)
+            .socket_group(self.socket_group()?)
+            .file_descriptor_name(self.file_descriptor_name()?)
+            .build();
+        Ok(socket_props)
+    }
+}
+
+impl SystemdSocketProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<SocketProps> {
+        let socket_props = SocketProps::builder()
+            .listen(self.listen().await?)
+            .accept(self.accept().await?)
+            .n_connections(self.n_connections().await?)
+            .n_accepted(self.n_accepted().await?)
+            .n_refused(self.n_refused().await?)
+            .result(self.result().await?)
+            .control_pid(self.control_pid().await?)
+            .backlog(self.backlog().await?)
+            .max_connections(self.max_connections().await?)
+            .socket_mode(self.socket_mode().await?)
+            .socket_user(self.socket_user().await?)
+            .socket_group(self.socket_group().await?)
+            .file_descriptor_name(self.file_descriptor_name().await?)
+            .build();
+        Ok(socket_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdSocketProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdSocketProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdSocketProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdSocketProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
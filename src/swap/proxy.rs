@@ -0,0 +1,72 @@
+use crate::{Result, SwapProps, Unit, UnitType};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Swap",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdSwap {
+    #[dbus_proxy(property)]
+    fn what(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn priority(&self) -> zbus::Result<i32>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property, name = "ControlPID")]
+    fn control_pid(&self) -> zbus::Result<u32>;
+}
+
+impl SystemdSwapProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<SwapProps> {
+        let swap_props = SwapProps::builder()
+            .what(self.what()?)
+            .priority(self.priority()?)
+            .result(self.result()?)
+            .control_pid(self.control_pid()?)
+            .build();
+        Ok(swap_props)
+    }
+}
+
+impl SystemdSwapProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<SwapProps> {
+        let swap_props = SwapProps::builder()
+            .what(self.what().await?)
+            .priority(self.priority().await?)
+            .result(self.result().await?)
+            .control_pid(self.control_pid().await?)
+            .build();
+        Ok(swap_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdSwapProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdSwapProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdSwapProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdSwapProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
+
+// proxy of a swap unit from the manager's unit listing
+pub async fn build_nonblock_proxy_from_unit(unit: &Unit) -> Result<SystemdSwapProxy<'static>> {
+    unit.check_unit_type(UnitType::Swap)?;
+    build_nonblock_proxy(unit.object_path.clone()).await
+}
+
+pub fn build_blocking_proxy_from_unit(unit: &Unit) -> Result<SystemdSwapProxyBlocking<'static>> {
+    unit.check_unit_type(UnitType::Swap)?;
+    build_blocking_proxy(unit.object_path.clone())
+}
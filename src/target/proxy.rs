@@ -0,0 +1,29 @@
+use crate::Result;
+
+// target units have no properties of their own, see SystemdUnitProxy for the generic ones
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Target",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdTarget {}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdTargetProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdTargetProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdTargetProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdTargetProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
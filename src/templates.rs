@@ -1,16 +1,228 @@
-use std::fmt::Display;
+mod drop_in;
+mod exec;
+mod mount;
+mod path;
+mod resource_control;
+mod security;
+mod slice;
+mod socket;
+mod timer;
+mod unit_file;
+
+pub use drop_in::*;
+pub use exec::*;
+pub use mount::*;
+pub use path::*;
+pub use resource_control::*;
+pub use security::*;
+pub use slice::*;
+pub use socket::*;
+pub use timer::*;
+pub use unit_file::*;
+
+use crate::{escape_specifiers, ErrorImpl, Result};
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+// systemd boolean
+fn yes_no(value: bool) -> &'static str {
+    match value {
+        true => "yes",
+        false => "no",
+    }
+}
+
+// space separated list, omitted when empty
+fn write_list(f: &mut std::fmt::Formatter<'_>, key: &str, values: &[&str]) -> std::fmt::Result {
+    if values.is_empty() {
+        return Ok(());
+    }
+    writeln!(f, "{}={}", key, values.join(" "))
+}
+
+// one assignment per value, for settings where a leading "~" only applies to the whole value
+fn write_each<T: Display>(
+    f: &mut std::fmt::Formatter<'_>,
+    key: &str,
+    values: &[T],
+) -> std::fmt::Result {
+    for value in values.iter() {
+        writeln!(f, "{}={}", key, value)?;
+    }
+    Ok(())
+}
+
+// empty assignments, written right after the section header so they precede any new value
+fn write_resets(f: &mut std::fmt::Formatter<'_>, keys: &[&str]) -> std::fmt::Result {
+    for key in keys.iter() {
+        writeln!(f, "{}=", key)?;
+    }
+    Ok(())
+}
+
+// C-style escapes understood by systemd when unquoting a word, "%" is doubled so that
+// specifiers are not expanded, "$" is doubled only where variables would be substituted
+fn write_escaped(
+    f: &mut std::fmt::Formatter<'_>,
+    value: &str,
+    escape_dollar: bool,
+) -> std::fmt::Result {
+    for c in value.chars() {
+        match c {
+            '%' => f.write_str("%%")?,
+            '$' if escape_dollar => f.write_str("$$")?,
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn is_variable_reference(arg: &str) -> bool {
+    match arg.strip_prefix("${").and_then(|arg| arg.strip_suffix('}')) {
+        Some(name) => {
+            !name.is_empty()
+                && !name.starts_with(|c: char| c.is_ascii_digit())
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        }
+        None => false,
+    }
+}
+
+// command line of ExecStart= and friends, arguments are quoted when they would be split or unescaped
+fn write_command(f: &mut std::fmt::Formatter<'_>, key: &str, args: &[&str]) -> std::fmt::Result {
+    write!(f, "{}=", key)?;
+    for (i, arg) in args.iter().enumerate() {
+        if i > 0 {
+            f.write_char(' ')?;
+        }
+        // a lone ";" separates commands
+        if *arg == ";" {
+            f.write_str("\\;")?;
+            continue;
+        }
+        // an argument consisting solely of "${NAME}" is kept as a variable reference,
+        // "$" anywhere else is taken literally
+        if is_variable_reference(arg) {
+            f.write_str(arg)?;
+            continue;
+        }
+        let quote = arg.is_empty()
+            || arg
+                .chars()
+                .any(|c| c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '\\'));
+        if quote {
+            f.write_char('"')?;
+        }
+        write_escaped(f, arg, true)?;
+        if quote {
+            f.write_char('"')?;
+        }
+    }
+    writeln!(f)
+}
 
 // configuration templates of systemd
+//
+// free-form text is written literally: "%" is doubled in Description=, Environment=,
+// SetCredential= and the command lines of Exec*= so specifiers are never expanded there. All other values, i.e. unit
+// names, paths, user and group names, are written as given so that templates can use specifiers
+// like "%i" or "%h" in them, pass them through `escape_specifiers` for a literal "%".
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#Specifiers
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#%5BUnit%5D%20Section%20Options
 pub struct UnitConfiguration<'a> {
     pub description: &'a str,
+    pub documentation: Vec<&'a str>,
+    pub requires: Vec<&'a str>,
+    pub wants: Vec<&'a str>,
+    pub requisite: Vec<&'a str>,
+    pub binds_to: Vec<&'a str>,
+    pub part_of: Vec<&'a str>,
+    pub upholds: Vec<&'a str>,
+    pub conflicts: Vec<&'a str>,
+    pub before: Vec<&'a str>,
     pub after: Vec<&'a str>,
+    pub on_failure: Vec<&'a str>,
+    pub on_success: Vec<&'a str>,
+    pub start_limit_interval_sec: Option<&'a str>,
+    pub start_limit_burst: Option<u32>,
+    pub job_timeout_sec: Option<&'a str>,
+    pub default_dependencies: Option<bool>,
+    pub stop_when_unneeded: Option<bool>,
+    pub refuse_manual_start: Option<bool>,
+    pub refuse_manual_stop: Option<bool>,
+    pub allow_isolate: Option<bool>,
+    pub ignore_on_isolate: Option<bool>,
+    pub conditions: Vec<Condition<'a>>,
+    pub asserts: Vec<Condition<'a>>,
+    // settings reset with an empty assignment, e.g. to drop list entries of the unit file in a drop-in
+    pub resets: Vec<&'a str>,
 }
 
 impl<'a> Display for UnitConfiguration<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "[Unit]")?;
-        writeln!(f, "Description={}", self.description)?;
-        writeln!(f, "After={}", self.after.join(" "))
+        write_resets(f, &self.resets)?;
+        if !self.description.is_empty() {
+            // Description= is not unescaped, so line breaks can only be flattened
+            let description: String = self
+                .description
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect();
+            writeln!(f, "Description={}", escape_specifiers(&description))?;
+        }
+        write_list(f, "Documentation", &self.documentation)?;
+        write_list(f, "Requires", &self.requires)?;
+        write_list(f, "Wants", &self.wants)?;
+        write_list(f, "Requisite", &self.requisite)?;
+        write_list(f, "BindsTo", &self.binds_to)?;
+        write_list(f, "PartOf", &self.part_of)?;
+        write_list(f, "Upholds", &self.upholds)?;
+        write_list(f, "Conflicts", &self.conflicts)?;
+        write_list(f, "Before", &self.before)?;
+        write_list(f, "After", &self.after)?;
+        write_list(f, "OnFailure", &self.on_failure)?;
+        write_list(f, "OnSuccess", &self.on_success)?;
+        if let Some(start_limit_interval_sec) = self.start_limit_interval_sec {
+            writeln!(f, "StartLimitIntervalSec={}", start_limit_interval_sec)?;
+        }
+        if let Some(start_limit_burst) = self.start_limit_burst {
+            writeln!(f, "StartLimitBurst={}", start_limit_burst)?;
+        }
+        if let Some(job_timeout_sec) = self.job_timeout_sec {
+            writeln!(f, "JobTimeoutSec={}", job_timeout_sec)?;
+        }
+        if let Some(default_dependencies) = self.default_dependencies {
+            writeln!(f, "DefaultDependencies={}", yes_no(default_dependencies))?;
+        }
+        if let Some(stop_when_unneeded) = self.stop_when_unneeded {
+            writeln!(f, "StopWhenUnneeded={}", yes_no(stop_when_unneeded))?;
+        }
+        if let Some(refuse_manual_start) = self.refuse_manual_start {
+            writeln!(f, "RefuseManualStart={}", yes_no(refuse_manual_start))?;
+        }
+        if let Some(refuse_manual_stop) = self.refuse_manual_stop {
+            writeln!(f, "RefuseManualStop={}", yes_no(refuse_manual_stop))?;
+        }
+        if let Some(allow_isolate) = self.allow_isolate {
+            writeln!(f, "AllowIsolate={}", yes_no(allow_isolate))?;
+        }
+        if let Some(ignore_on_isolate) = self.ignore_on_isolate {
+            writeln!(f, "IgnoreOnIsolate={}", yes_no(ignore_on_isolate))?;
+        }
+        for condition in self.conditions.iter() {
+            writeln!(f, "Condition{}={}", condition.ty, condition)?;
+        }
+        for assert in self.asserts.iter() {
+            writeln!(f, "Assert{}={}", assert.ty, assert)?;
+        }
+        Ok(())
     }
 }
 
@@ -23,7 +235,30 @@ impl<'a> UnitConfiguration<'a> {
 #[derive(Default)]
 pub struct UnitConfigurationBuilder<'a> {
     pub description: &'a str,
+    pub documentation: Vec<&'a str>,
+    pub requires: Vec<&'a str>,
+    pub wants: Vec<&'a str>,
+    pub requisite: Vec<&'a str>,
+    pub binds_to: Vec<&'a str>,
+    pub part_of: Vec<&'a str>,
+    pub upholds: Vec<&'a str>,
+    pub conflicts: Vec<&'a str>,
+    pub before: Vec<&'a str>,
     pub after: Vec<&'a str>,
+    pub on_failure: Vec<&'a str>,
+    pub on_success: Vec<&'a str>,
+    pub start_limit_interval_sec: Option<&'a str>,
+    pub start_limit_burst: Option<u32>,
+    pub job_timeout_sec: Option<&'a str>,
+    pub default_dependencies: Option<bool>,
+    pub stop_when_unneeded: Option<bool>,
+    pub refuse_manual_start: Option<bool>,
+    pub refuse_manual_stop: Option<bool>,
+    pub allow_isolate: Option<bool>,
+    pub ignore_on_isolate: Option<bool>,
+    pub conditions: Vec<Condition<'a>>,
+    pub asserts: Vec<Condition<'a>>,
+    pub resets: Vec<&'a str>,
 }
 
 impl<'a> UnitConfigurationBuilder<'a> {
@@ -32,15 +267,304 @@ impl<'a> UnitConfigurationBuilder<'a> {
         self
     }
 
+    pub fn documentation(mut self, documentation: &'a str) -> Self {
+        self.documentation.push(documentation);
+        self
+    }
+
+    pub fn requires(mut self, requires: &'a str) -> Self {
+        self.requires.push(requires);
+        self
+    }
+
+    pub fn wants(mut self, wants: &'a str) -> Self {
+        self.wants.push(wants);
+        self
+    }
+
+    pub fn requisite(mut self, requisite: &'a str) -> Self {
+        self.requisite.push(requisite);
+        self
+    }
+
+    pub fn binds_to(mut self, binds_to: &'a str) -> Self {
+        self.binds_to.push(binds_to);
+        self
+    }
+
+    pub fn part_of(mut self, part_of: &'a str) -> Self {
+        self.part_of.push(part_of);
+        self
+    }
+
+    pub fn upholds(mut self, upholds: &'a str) -> Self {
+        self.upholds.push(upholds);
+        self
+    }
+
+    pub fn conflicts(mut self, conflicts: &'a str) -> Self {
+        self.conflicts.push(conflicts);
+        self
+    }
+
+    pub fn before(mut self, before: &'a str) -> Self {
+        self.before.push(before);
+        self
+    }
+
     pub fn after(mut self, after: &'a str) -> Self {
         self.after.push(after);
         self
     }
 
+    pub fn on_failure(mut self, on_failure: &'a str) -> Self {
+        self.on_failure.push(on_failure);
+        self
+    }
+
+    pub fn on_success(mut self, on_success: &'a str) -> Self {
+        self.on_success.push(on_success);
+        self
+    }
+
+    pub fn start_limit_interval_sec(mut self, start_limit_interval_sec: &'a str) -> Self {
+        self.start_limit_interval_sec = Some(start_limit_interval_sec);
+        self
+    }
+
+    pub fn start_limit_burst(mut self, start_limit_burst: u32) -> Self {
+        self.start_limit_burst = Some(start_limit_burst);
+        self
+    }
+
+    pub fn job_timeout_sec(mut self, job_timeout_sec: &'a str) -> Self {
+        self.job_timeout_sec = Some(job_timeout_sec);
+        self
+    }
+
+    pub fn default_dependencies(mut self, default_dependencies: bool) -> Self {
+        self.default_dependencies = Some(default_dependencies);
+        self
+    }
+
+    pub fn stop_when_unneeded(mut self, stop_when_unneeded: bool) -> Self {
+        self.stop_when_unneeded = Some(stop_when_unneeded);
+        self
+    }
+
+    pub fn refuse_manual_start(mut self, refuse_manual_start: bool) -> Self {
+        self.refuse_manual_start = Some(refuse_manual_start);
+        self
+    }
+
+    pub fn refuse_manual_stop(mut self, refuse_manual_stop: bool) -> Self {
+        self.refuse_manual_stop = Some(refuse_manual_stop);
+        self
+    }
+
+    pub fn allow_isolate(mut self, allow_isolate: bool) -> Self {
+        self.allow_isolate = Some(allow_isolate);
+        self
+    }
+
+    pub fn ignore_on_isolate(mut self, ignore_on_isolate: bool) -> Self {
+        self.ignore_on_isolate = Some(ignore_on_isolate);
+        self
+    }
+
+    pub fn condition(mut self, condition: Condition<'a>) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn assert(mut self, assert: Condition<'a>) -> Self {
+        self.asserts.push(assert);
+        self
+    }
+
+    // render "Key=" to reset a setting, e.g. list entries inherited from the unit file
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
     pub fn build(self) -> UnitConfiguration<'a> {
         let description = self.description;
+        let documentation = self.documentation;
+        let requires = self.requires;
+        let wants = self.wants;
+        let requisite = self.requisite;
+        let binds_to = self.binds_to;
+        let part_of = self.part_of;
+        let upholds = self.upholds;
+        let conflicts = self.conflicts;
+        let before = self.before;
         let after = self.after;
-        UnitConfiguration { description, after }
+        let on_failure = self.on_failure;
+        let on_success = self.on_success;
+        let start_limit_interval_sec = self.start_limit_interval_sec;
+        let start_limit_burst = self.start_limit_burst;
+        let job_timeout_sec = self.job_timeout_sec;
+        let default_dependencies = self.default_dependencies;
+        let stop_when_unneeded = self.stop_when_unneeded;
+        let refuse_manual_start = self.refuse_manual_start;
+        let refuse_manual_stop = self.refuse_manual_stop;
+        let allow_isolate = self.allow_isolate;
+        let ignore_on_isolate = self.ignore_on_isolate;
+        let conditions = self.conditions;
+        let asserts = self.asserts;
+        UnitConfiguration {
+            description,
+            documentation,
+            requires,
+            wants,
+            requisite,
+            binds_to,
+            part_of,
+            upholds,
+            conflicts,
+            before,
+            after,
+            on_failure,
+            on_success,
+            start_limit_interval_sec,
+            start_limit_burst,
+            job_timeout_sec,
+            default_dependencies,
+            stop_when_unneeded,
+            refuse_manual_start,
+            refuse_manual_stop,
+            allow_isolate,
+            ignore_on_isolate,
+            conditions,
+            asserts,
+            resets: self.resets,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#Conditions%20and%20Asserts
+pub enum ConditionType {
+    PathExists,
+    PathIsDirectory,
+    FileNotEmpty,
+    Virtualization,
+    Host,
+    KernelCommandLine,
+    Architecture,
+    User,
+    Group,
+    Environment,
+}
+
+impl Display for ConditionType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ty = match self {
+            ConditionType::PathExists => "PathExists",
+            ConditionType::PathIsDirectory => "PathIsDirectory",
+            ConditionType::FileNotEmpty => "FileNotEmpty",
+            ConditionType::Virtualization => "Virtualization",
+            ConditionType::Host => "Host",
+            ConditionType::KernelCommandLine => "KernelCommandLine",
+            ConditionType::Architecture => "Architecture",
+            ConditionType::User => "User",
+            ConditionType::Group => "Group",
+            ConditionType::Environment => "Environment",
+        };
+        write!(f, "{}", ty)
+    }
+}
+
+// the part of the key after "Condition" or "Assert"
+impl FromStr for ConditionType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let ty = match s {
+            "PathExists" => ConditionType::PathExists,
+            "PathIsDirectory" => ConditionType::PathIsDirectory,
+            "FileNotEmpty" => ConditionType::FileNotEmpty,
+            "Virtualization" => ConditionType::Virtualization,
+            "Host" => ConditionType::Host,
+            "KernelCommandLine" => ConditionType::KernelCommandLine,
+            "Architecture" => ConditionType::Architecture,
+            "User" => ConditionType::User,
+            "Group" => ConditionType::Group,
+            "Environment" => ConditionType::Environment,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "condition type").into()),
+        };
+        Ok(ty)
+    }
+}
+
+// rendered as Condition<ty>= or Assert<ty>= depending on where it is added to the unit
+pub struct Condition<'a> {
+    pub ty: ConditionType,
+    pub value: &'a str,
+    // "!" prefix, the condition holds if the check fails
+    pub negate: bool,
+    // "|" prefix, at least one of the triggering conditions has to hold
+    pub trigger: bool,
+}
+
+impl<'a> Condition<'a> {
+    pub fn builder() -> ConditionBuilder<'a> {
+        ConditionBuilder::default()
+    }
+}
+
+impl<'a> Display for Condition<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.trigger {
+            write!(f, "|")?;
+        }
+        if self.negate {
+            write!(f, "!")?;
+        }
+        write!(f, "{}", self.value)
+    }
+}
+
+#[derive(Default)]
+pub struct ConditionBuilder<'a> {
+    pub ty: Option<ConditionType>,
+    pub value: Option<&'a str>,
+    pub negate: bool,
+    pub trigger: bool,
+}
+
+impl<'a> ConditionBuilder<'a> {
+    pub fn ty(mut self, ty: ConditionType) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    pub fn value(mut self, value: &'a str) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn negate(mut self) -> Self {
+        self.negate = true;
+        self
+    }
+
+    pub fn trigger(mut self) -> Self {
+        self.trigger = true;
+        self
+    }
+
+    pub fn build(self) -> Condition<'a> {
+        let ty = self.ty.expect("type undefined");
+        let value = self.value.expect("value undefined");
+        let negate = self.negate;
+        let trigger = self.trigger;
+        Condition {
+            ty,
+            value,
+            negate,
+            trigger,
+        }
     }
 }
 
@@ -48,6 +572,11 @@ pub enum ServiceType {
     Simple,
     Exec,
     Forking,
+    Oneshot,
+    Dbus,
+    Notify,
+    NotifyReload,
+    Idle,
 }
 
 impl Display for ServiceType {
@@ -56,11 +585,35 @@ impl Display for ServiceType {
             ServiceType::Simple => "simple",
             ServiceType::Exec => "exec",
             ServiceType::Forking => "forking",
+            ServiceType::Oneshot => "oneshot",
+            ServiceType::Dbus => "dbus",
+            ServiceType::Notify => "notify",
+            ServiceType::NotifyReload => "notify-reload",
+            ServiceType::Idle => "idle",
         };
         write!(f, "{}", ty)
     }
 }
 
+impl FromStr for ServiceType {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = match s {
+            "simple" => ServiceType::Simple,
+            "exec" => ServiceType::Exec,
+            "forking" => ServiceType::Forking,
+            "oneshot" => ServiceType::Oneshot,
+            "dbus" => ServiceType::Dbus,
+            "notify" => ServiceType::Notify,
+            "notify-reload" => ServiceType::NotifyReload,
+            "idle" => ServiceType::Idle,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "Type=").into()),
+        };
+        Ok(value)
+    }
+}
+
 pub enum RestartPolicy {
     No,
     OnSuccess,
@@ -80,6 +633,89 @@ impl Display for RestartPolicy {
     }
 }
 
+impl FromStr for RestartPolicy {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = match s {
+            "no" => RestartPolicy::No,
+            "on-success" => RestartPolicy::OnSuccess,
+            "on-failure" => RestartPolicy::OnFailure,
+            "always" => RestartPolicy::Always,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "Restart=").into()),
+        };
+        Ok(value)
+    }
+}
+
+pub enum KillMode {
+    ControlGroup,
+    Mixed,
+    Process,
+    None,
+}
+
+impl Display for KillMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mode = match self {
+            KillMode::ControlGroup => "control-group",
+            KillMode::Mixed => "mixed",
+            KillMode::Process => "process",
+            KillMode::None => "none",
+        };
+        write!(f, "{}", mode)
+    }
+}
+
+impl FromStr for KillMode {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = match s {
+            "control-group" => KillMode::ControlGroup,
+            "mixed" => KillMode::Mixed,
+            "process" => KillMode::Process,
+            "none" => KillMode::None,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "KillMode=").into()),
+        };
+        Ok(value)
+    }
+}
+
+pub enum NotifyAccess {
+    None,
+    Main,
+    Exec,
+    All,
+}
+
+impl Display for NotifyAccess {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let access = match self {
+            NotifyAccess::None => "none",
+            NotifyAccess::Main => "main",
+            NotifyAccess::Exec => "exec",
+            NotifyAccess::All => "all",
+        };
+        write!(f, "{}", access)
+    }
+}
+
+impl FromStr for NotifyAccess {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = match s {
+            "none" => NotifyAccess::None,
+            "main" => NotifyAccess::Main,
+            "exec" => NotifyAccess::Exec,
+            "all" => NotifyAccess::All,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "NotifyAccess=").into()),
+        };
+        Ok(value)
+    }
+}
+
 pub struct EnvironmentVariable<'a> {
     pub key: &'a str,
     pub value: &'a str,
@@ -144,11 +780,45 @@ pub struct ServiceConfiguration<'a> {
     pub user: Option<&'a str>,
     pub group: Option<&'a str>,
     pub envs: Vec<EnvironmentVariable<'a>>,
+    pub pid_file: Option<&'a str>,
+    pub remain_after_exit: Option<bool>,
+    // each hook is a command line, hooks are executed in order
+    pub exec_start_pre: Vec<Vec<&'a str>>,
+    pub exec_start_post: Vec<Vec<&'a str>>,
+    pub exec_reload: Vec<Vec<&'a str>>,
+    pub exec_stop: Vec<Vec<&'a str>>,
+    pub exec_stop_post: Vec<Vec<&'a str>>,
+    pub timeout_start_sec: Option<&'a str>,
+    pub timeout_stop_sec: Option<&'a str>,
+    // exit status codes or signal names, e.g. "75", "SIGHUP"
+    pub success_exit_status: Vec<&'a str>,
+    pub kill_mode: Option<KillMode>,
+    pub kill_signal: Option<&'a str>,
+    pub notify_access: Option<NotifyAccess>,
+    pub exec: ExecConfiguration<'a>,
+    pub security: SecurityConfiguration<'a>,
+    pub resource_control: ResourceControlConfiguration<'a>,
+    pub resets: Vec<&'a str>,
 }
 
 impl<'a> Display for ServiceConfiguration<'a> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_section(f, &[])
+    }
+}
+
+impl<'a> ServiceConfiguration<'a> {
+    pub fn builder() -> ServiceConfigurationBuilder<'a> {
+        ServiceConfigurationBuilder::default()
+    }
+
+    // omit lists the keys of Type=, Restart= and RestartSec= to leave out
+    fn write_section(&self, f: &mut std::fmt::Formatter<'_>, omit: &[&str]) -> std::fmt::Result {
         writeln!(f, "[Service]")?;
+        write_resets(f, &self.resets)?;
+        if !omit.contains(&"Type") {
+            writeln!(f, "Type={}", self.ty)?;
+        }
         if let Some(working_directory) = self.working_directory {
             writeln!(f, "WorkingDirectory={}", working_directory)?;
         }
@@ -159,49 +829,108 @@ impl<'a> Display for ServiceConfiguration<'a> {
             writeln!(f, "Group={}", group)?;
         }
         for env in self.envs.iter() {
-            writeln!(f, r#"Environment="{}""#, env)?;
+            f.write_str("Environment=\"")?;
+            write_escaped(f, &env.to_string(), false)?;
+            writeln!(f, "\"")?;
+        }
+        if let Some(pid_file) = self.pid_file {
+            writeln!(f, "PIDFile={}", pid_file)?;
+        }
+        if let Some(remain_after_exit) = self.remain_after_exit {
+            writeln!(f, "RemainAfterExit={}", yes_no(remain_after_exit))?;
+        }
+        for exec_start_pre in self.exec_start_pre.iter() {
+            write_command(f, "ExecStartPre", exec_start_pre)?;
+        }
+        if !self.exec_start.is_empty() {
+            write_command(f, "ExecStart", &self.exec_start)?;
+        }
+        for exec_start_post in self.exec_start_post.iter() {
+            write_command(f, "ExecStartPost", exec_start_post)?;
+        }
+        for exec_reload in self.exec_reload.iter() {
+            write_command(f, "ExecReload", exec_reload)?;
+        }
+        for exec_stop in self.exec_stop.iter() {
+            write_command(f, "ExecStop", exec_stop)?;
+        }
+        for exec_stop_post in self.exec_stop_post.iter() {
+            write_command(f, "ExecStopPost", exec_stop_post)?;
+        }
+        if !omit.contains(&"Restart") {
+            writeln!(f, "Restart={}", self.restart_policy)?;
+        }
+        if !omit.contains(&"RestartSec") {
+            writeln!(f, "RestartSec={}", self.restart_sec)?;
+        }
+        if let Some(timeout_start_sec) = self.timeout_start_sec {
+            writeln!(f, "TimeoutStartSec={}", timeout_start_sec)?;
+        }
+        if let Some(timeout_stop_sec) = self.timeout_stop_sec {
+            writeln!(f, "TimeoutStopSec={}", timeout_stop_sec)?;
+        }
+        write_list(f, "SuccessExitStatus", &self.success_exit_status)?;
+        if let Some(kill_mode) = &self.kill_mode {
+            writeln!(f, "KillMode={}", kill_mode)?;
+        }
+        if let Some(kill_signal) = self.kill_signal {
+            writeln!(f, "KillSignal={}", kill_signal)?;
         }
-        writeln!(f, "ExecStart={}", self.exec_start.join(" "))?;
-        writeln!(f, "Restart={}", self.restart_policy)?;
-        writeln!(f, "RestartSec={}", self.restart_sec)
+        if let Some(notify_access) = &self.notify_access {
+            writeln!(f, "NotifyAccess={}", notify_access)?;
+        }
+        write!(f, "{}", self.exec)?;
+        write!(f, "{}", self.security)?;
+        write!(f, "{}", self.resource_control)
     }
 }
 
-impl<'a> ServiceConfiguration<'a> {
-    pub fn builder() -> ServiceConfigurationBuilder<'a> {
-        ServiceConfigurationBuilder::default()
+// [Service] section of a drop-in, the full unit file defaults are only written if set explicitly
+pub struct ServiceDropInConfiguration<'a> {
+    pub service: ServiceConfiguration<'a>,
+    // keys of Type=, Restart= and RestartSec= that were left unset
+    pub unset: Vec<&'static str>,
+}
+
+impl<'a> Display for ServiceDropInConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.service.write_section(f, &self.unset)
     }
 }
 
+// None means unset, build() falls back to the defaults, build_drop_in() leaves them out
+#[derive(Default)]
 pub struct ServiceConfigurationBuilder<'a> {
-    pub ty: ServiceType,
+    pub ty: Option<ServiceType>,
     pub exec_start: Vec<&'a str>,
-    pub restart_policy: RestartPolicy,
-    pub restart_sec: &'a str,
+    pub restart_policy: Option<RestartPolicy>,
+    pub restart_sec: Option<&'a str>,
     pub working_directory: Option<&'a str>,
     pub user: Option<&'a str>,
     pub group: Option<&'a str>,
     pub envs: Vec<EnvironmentVariable<'a>>,
-}
-
-impl<'a> Default for ServiceConfigurationBuilder<'a> {
-    fn default() -> Self {
-        ServiceConfigurationBuilder {
-            ty: ServiceType::Simple,
-            exec_start: vec![],
-            restart_policy: RestartPolicy::No,
-            restart_sec: "100ms",
-            working_directory: None,
-            user: None,
-            group: None,
-            envs: vec![],
-        }
-    }
+    pub pid_file: Option<&'a str>,
+    pub remain_after_exit: Option<bool>,
+    pub exec_start_pre: Vec<Vec<&'a str>>,
+    pub exec_start_post: Vec<Vec<&'a str>>,
+    pub exec_reload: Vec<Vec<&'a str>>,
+    pub exec_stop: Vec<Vec<&'a str>>,
+    pub exec_stop_post: Vec<Vec<&'a str>>,
+    pub timeout_start_sec: Option<&'a str>,
+    pub timeout_stop_sec: Option<&'a str>,
+    pub success_exit_status: Vec<&'a str>,
+    pub kill_mode: Option<KillMode>,
+    pub kill_signal: Option<&'a str>,
+    pub notify_access: Option<NotifyAccess>,
+    pub exec: ExecConfigurationBuilder<'a>,
+    pub security: SecurityConfigurationBuilder<'a>,
+    pub resource_control: ResourceControlConfigurationBuilder<'a>,
+    pub resets: Vec<&'a str>,
 }
 
 impl<'a> ServiceConfigurationBuilder<'a> {
     pub fn ty(mut self, ty: ServiceType) -> Self {
-        self.ty = ty;
+        self.ty = Some(ty);
         self
     }
 
@@ -211,12 +940,12 @@ impl<'a> ServiceConfigurationBuilder<'a> {
     }
 
     pub fn restart_policy(mut self, restart_policy: RestartPolicy) -> Self {
-        self.restart_policy = restart_policy;
+        self.restart_policy = Some(restart_policy);
         self
     }
 
     pub fn restart_sec(mut self, restart_sec: &'a str) -> Self {
-        self.restart_sec = restart_sec;
+        self.restart_sec = Some(restart_sec);
         self
     }
 
@@ -241,15 +970,141 @@ impl<'a> ServiceConfigurationBuilder<'a> {
         self
     }
 
+    pub fn pid_file(mut self, pid_file: &'a str) -> Self {
+        self.pid_file = Some(pid_file);
+        self
+    }
+
+    pub fn remain_after_exit(mut self, remain_after_exit: bool) -> Self {
+        self.remain_after_exit = Some(remain_after_exit);
+        self
+    }
+
+    pub fn exec_start_pre(mut self, exec_start_pre: Vec<&'a str>) -> Self {
+        self.exec_start_pre.push(exec_start_pre);
+        self
+    }
+
+    pub fn exec_start_post(mut self, exec_start_post: Vec<&'a str>) -> Self {
+        self.exec_start_post.push(exec_start_post);
+        self
+    }
+
+    pub fn exec_reload(mut self, exec_reload: Vec<&'a str>) -> Self {
+        self.exec_reload.push(exec_reload);
+        self
+    }
+
+    pub fn exec_stop(mut self, exec_stop: Vec<&'a str>) -> Self {
+        self.exec_stop.push(exec_stop);
+        self
+    }
+
+    pub fn exec_stop_post(mut self, exec_stop_post: Vec<&'a str>) -> Self {
+        self.exec_stop_post.push(exec_stop_post);
+        self
+    }
+
+    pub fn timeout_start_sec(mut self, timeout_start_sec: &'a str) -> Self {
+        self.timeout_start_sec = Some(timeout_start_sec);
+        self
+    }
+
+    pub fn timeout_stop_sec(mut self, timeout_stop_sec: &'a str) -> Self {
+        self.timeout_stop_sec = Some(timeout_stop_sec);
+        self
+    }
+
+    pub fn success_exit_status(mut self, success_exit_status: &'a str) -> Self {
+        self.success_exit_status.push(success_exit_status);
+        self
+    }
+
+    pub fn kill_mode(mut self, kill_mode: KillMode) -> Self {
+        self.kill_mode = Some(kill_mode);
+        self
+    }
+
+    pub fn kill_signal(mut self, kill_signal: &'a str) -> Self {
+        self.kill_signal = Some(kill_signal);
+        self
+    }
+
+    pub fn notify_access(mut self, notify_access: NotifyAccess) -> Self {
+        self.notify_access = Some(notify_access);
+        self
+    }
+
+    pub fn exec(mut self, exec: ExecConfigurationBuilder<'a>) -> Self {
+        self.exec = exec;
+        self
+    }
+
+    pub fn security(mut self, security: SecurityConfigurationBuilder<'a>) -> Self {
+        self.security = security;
+        self
+    }
+
+    pub fn resource_control(
+        mut self,
+        resource_control: ResourceControlConfigurationBuilder<'a>,
+    ) -> Self {
+        self.resource_control = resource_control;
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    // partial [Service] section of a drop-in, without the defaults of a full unit file
+    pub fn build_drop_in(mut self) -> ServiceDropInConfiguration<'a> {
+        // ExecStart= accumulates across files, so a new command replaces the inherited one
+        if !self.exec_start.is_empty() && !self.resets.contains(&"ExecStart") {
+            self.resets.insert(0, "ExecStart");
+        }
+        let unset = [
+            ("Type", self.ty.is_none()),
+            ("Restart", self.restart_policy.is_none()),
+            ("RestartSec", self.restart_sec.is_none()),
+        ];
+        let unset = unset
+            .into_iter()
+            .filter_map(|(key, unset)| unset.then_some(key))
+            .collect();
+        ServiceDropInConfiguration {
+            service: self.build(),
+            unset,
+        }
+    }
+
     pub fn build(self) -> ServiceConfiguration<'a> {
-        let ty = self.ty;
+        let ty = self.ty.unwrap_or(ServiceType::Simple);
         let exec_start = self.exec_start;
-        let restart_policy = self.restart_policy;
-        let restart_sec = self.restart_sec;
+        let restart_policy = self.restart_policy.unwrap_or(RestartPolicy::No);
+        let restart_sec = self.restart_sec.unwrap_or("100ms");
         let working_directory = self.working_directory;
         let user = self.user;
         let group = self.group;
         let envs = self.envs;
+        let pid_file = self.pid_file;
+        let remain_after_exit = self.remain_after_exit;
+        let exec_start_pre = self.exec_start_pre;
+        let exec_start_post = self.exec_start_post;
+        let exec_reload = self.exec_reload;
+        let exec_stop = self.exec_stop;
+        let exec_stop_post = self.exec_stop_post;
+        let timeout_start_sec = self.timeout_start_sec;
+        let timeout_stop_sec = self.timeout_stop_sec;
+        let success_exit_status = self.success_exit_status;
+        let kill_mode = self.kill_mode;
+        let kill_signal = self.kill_signal;
+        let notify_access = self.notify_access;
+        let exec = self.exec.build();
+        let security = self.security.build();
+        let resource_control = self.resource_control.build();
+        let resets = self.resets;
         ServiceConfiguration {
             ty,
             exec_start,
@@ -259,102 +1114,194 @@ impl<'a> ServiceConfigurationBuilder<'a> {
             user,
             group,
             envs,
+            pid_file,
+            remain_after_exit,
+            exec_start_pre,
+            exec_start_post,
+            exec_reload,
+            exec_stop,
+            exec_stop_post,
+            timeout_start_sec,
+            timeout_stop_sec,
+            success_exit_status,
+            kill_mode,
+            kill_signal,
+            notify_access,
+            exec,
+            security,
+            resource_control,
+            resets,
         }
     }
 }
 
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#%5BInstall%5D%20Section%20Options
 pub struct InstallConfiguration<'a> {
     pub wanted_by: Vec<&'a str>,
-}
-
-impl<'a> Display for InstallConfiguration<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        writeln!(f, "[Install]")?;
-        writeln!(f, "WantedBy={}", self.wanted_by.join(" "))
-    }
+    pub required_by: Vec<&'a str>,
+    pub upheld_by: Vec<&'a str>,
+    pub alias: Vec<&'a str>,
+    pub also: Vec<&'a str>,
+    pub default_instance: Option<&'a str>,
+    pub resets: Vec<&'a str>,
 }
 
 impl<'a> InstallConfiguration<'a> {
     pub fn builder() -> InstallConfigurationBuilder<'a> {
         InstallConfigurationBuilder::default()
     }
-}
 
-pub struct InstallConfigurationBuilder<'a> {
-    pub wanted_by: Vec<&'a str>,
+    pub fn is_empty(&self) -> bool {
+        self.wanted_by.is_empty()
+            && self.required_by.is_empty()
+            && self.upheld_by.is_empty()
+            && self.alias.is_empty()
+            && self.also.is_empty()
+            && self.default_instance.is_none()
+            && self.resets.is_empty()
+    }
 }
 
-impl<'a> Default for InstallConfigurationBuilder<'a> {
-    fn default() -> Self {
-        InstallConfigurationBuilder {
-            // https://unix.stackexchange.com/questions/404667/systemd-service-what-is-multi-user-target
-            wanted_by: vec!["multi-user.target"],
+impl<'a> Display for InstallConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return Ok(());
+        }
+        writeln!(f, "[Install]")?;
+        write_resets(f, &self.resets)?;
+        write_list(f, "WantedBy", &self.wanted_by)?;
+        write_list(f, "RequiredBy", &self.required_by)?;
+        write_list(f, "UpheldBy", &self.upheld_by)?;
+        write_list(f, "Alias", &self.alias)?;
+        write_list(f, "Also", &self.also)?;
+        if let Some(default_instance) = self.default_instance {
+            writeln!(f, "DefaultInstance={}", default_instance)?;
         }
+        Ok(())
     }
 }
 
+#[derive(Default)]
+pub struct InstallConfigurationBuilder<'a> {
+    pub wanted_by: Vec<&'a str>,
+    // used as WantedBy= if no target is added explicitly, None falls back to the default target of
+    // the enclosing unit type, e.g. multi-user.target for services
+    pub default_wanted_by: Option<&'a str>,
+    // no WantedBy= fallback at all
+    pub no_default_wanted_by: bool,
+    pub required_by: Vec<&'a str>,
+    pub upheld_by: Vec<&'a str>,
+    pub alias: Vec<&'a str>,
+    pub also: Vec<&'a str>,
+    pub default_instance: Option<&'a str>,
+    pub resets: Vec<&'a str>,
+}
+
 impl<'a> InstallConfigurationBuilder<'a> {
     pub fn wanted_by(mut self, wanted_by: &'a str) -> Self {
         self.wanted_by.push(wanted_by);
         self
     }
 
-    pub fn build(self) -> InstallConfiguration<'a> {
-        let wanted_by = self.wanted_by;
-        InstallConfiguration { wanted_by }
+    pub fn default_wanted_by(mut self, default_wanted_by: &'a str) -> Self {
+        self.default_wanted_by = Some(default_wanted_by);
+        self
     }
-}
 
-// https://www.freedesktop.org/software/systemd/man/systemd.service.html#Service%20Templates
-pub struct ServiceUnitConfiguration<'a> {
-    pub unit: UnitConfiguration<'a>,
-    pub service: ServiceConfiguration<'a>,
-    pub install: InstallConfiguration<'a>,
-}
+    // do not fall back to any target when no WantedBy= is given
+    pub fn clear_default_wanted_by(mut self) -> Self {
+        self.no_default_wanted_by = true;
+        self
+    }
 
-impl<'a> Display for ServiceUnitConfiguration<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}\n{}\n{}", self.unit, self.service, self.install)
+    pub fn required_by(mut self, required_by: &'a str) -> Self {
+        self.required_by.push(required_by);
+        self
     }
-}
 
-impl<'a> ServiceUnitConfiguration<'a> {
-    pub fn builder() -> ServiceUnitConfigurationBuilder<'a> {
-        ServiceUnitConfigurationBuilder::default()
+    pub fn upheld_by(mut self, upheld_by: &'a str) -> Self {
+        self.upheld_by.push(upheld_by);
+        self
     }
-}
 
-#[derive(Default)]
-pub struct ServiceUnitConfigurationBuilder<'a> {
-    pub unit: UnitConfigurationBuilder<'a>,
-    pub service: ServiceConfigurationBuilder<'a>,
-    pub install: InstallConfigurationBuilder<'a>,
-}
+    pub fn alias(mut self, alias: &'a str) -> Self {
+        self.alias.push(alias);
+        self
+    }
 
-impl<'a> ServiceUnitConfigurationBuilder<'a> {
-    pub fn unit(mut self, unit: UnitConfigurationBuilder<'a>) -> Self {
-        self.unit = unit;
+    pub fn also(mut self, also: &'a str) -> Self {
+        self.also.push(also);
         self
     }
 
-    pub fn service(mut self, service: ServiceConfigurationBuilder<'a>) -> Self {
-        self.service = service;
+    pub fn default_instance(mut self, default_instance: &'a str) -> Self {
+        self.default_instance = Some(default_instance);
         self
     }
 
-    pub fn install(mut self, install: InstallConfigurationBuilder<'a>) -> Self {
-        self.install = install;
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
         self
     }
 
-    pub fn build(self) -> ServiceUnitConfiguration<'a> {
-        let unit = self.unit.build();
-        let service = self.service.build();
-        let install = self.install.build();
-        ServiceUnitConfiguration {
-            unit,
-            service,
-            install,
+    // a standalone [Install] section has no unit type, it falls back to multi-user.target as before
+    pub fn build(self) -> InstallConfiguration<'a> {
+        self.build_for_unit_type(Some("multi-user.target"))
+    }
+
+    // unit_default is the WantedBy= target of the enclosing unit type, if any
+    pub(crate) fn build_for_unit_type(
+        self,
+        unit_default: Option<&'a str>,
+    ) -> InstallConfiguration<'a> {
+        let default_wanted_by = match self.no_default_wanted_by {
+            true => None,
+            false => self.default_wanted_by.or(unit_default),
+        };
+        let wanted_by = match (self.wanted_by.is_empty(), default_wanted_by) {
+            (true, Some(default_wanted_by)) => vec![default_wanted_by],
+            _ => self.wanted_by,
+        };
+        let required_by = self.required_by;
+        let upheld_by = self.upheld_by;
+        let alias = self.alias;
+        let also = self.also;
+        let default_instance = self.default_instance;
+        let resets = self.resets;
+        InstallConfiguration {
+            wanted_by,
+            required_by,
+            upheld_by,
+            alias,
+            also,
+            default_instance,
+            resets,
+        }
+    }
+
+    // partial [Install] section of a drop-in, without the default WantedBy=
+    pub fn build_drop_in(self) -> InstallConfiguration<'a> {
+        InstallConfigurationBuilder {
+            no_default_wanted_by: true,
+            ..self
         }
+        .build()
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.service.html#Service%20Templates
+// since 0.3 the [Service] section is the generic `section` field, `service()` reads it
+pub type ServiceUnitConfiguration<'a> = UnitFile<'a, ServiceConfiguration<'a>>;
+pub type ServiceUnitConfigurationBuilder<'a> = UnitFileBuilder<'a, ServiceConfiguration<'a>>;
+
+impl<'a> ServiceUnitConfiguration<'a> {
+    pub fn service(&self) -> &ServiceConfiguration<'a> {
+        &self.section
+    }
+}
+
+impl<'a> ServiceUnitConfigurationBuilder<'a> {
+    pub fn service(self, service: ServiceConfigurationBuilder<'a>) -> Self {
+        self.section(service)
     }
 }
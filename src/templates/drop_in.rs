@@ -0,0 +1,130 @@
+use super::{
+    AutomountConfiguration, AutomountConfigurationBuilder, InstallConfiguration,
+    InstallConfigurationBuilder, MountConfiguration, MountConfigurationBuilder, PathConfiguration,
+    PathConfigurationBuilder, ServiceConfigurationBuilder, ServiceDropInConfiguration,
+    SliceConfiguration, SliceConfigurationBuilder, SocketConfiguration, SocketConfigurationBuilder,
+    TimerConfiguration, TimerConfigurationBuilder, UnitConfiguration, UnitConfigurationBuilder,
+};
+use std::fmt::Display;
+
+// section builder that renders only the settings it was given, for use in a drop-in
+pub trait DropInSection<'a> {
+    type Section: Display + 'a;
+
+    fn build_drop_in(self) -> Self::Section;
+}
+
+impl<'a> DropInSection<'a> for UnitConfigurationBuilder<'a> {
+    type Section = UnitConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        self.build()
+    }
+}
+
+impl<'a> DropInSection<'a> for ServiceConfigurationBuilder<'a> {
+    type Section = ServiceDropInConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        ServiceConfigurationBuilder::build_drop_in(self)
+    }
+}
+
+impl<'a> DropInSection<'a> for InstallConfigurationBuilder<'a> {
+    type Section = InstallConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        InstallConfigurationBuilder::build_drop_in(self)
+    }
+}
+
+impl<'a> DropInSection<'a> for TimerConfigurationBuilder<'a> {
+    type Section = TimerConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        self.build()
+    }
+}
+
+impl<'a> DropInSection<'a> for SocketConfigurationBuilder<'a> {
+    type Section = SocketConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        self.build()
+    }
+}
+
+impl<'a> DropInSection<'a> for MountConfigurationBuilder<'a> {
+    type Section = MountConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        MountConfigurationBuilder::build_drop_in(self)
+    }
+}
+
+impl<'a> DropInSection<'a> for AutomountConfigurationBuilder<'a> {
+    type Section = AutomountConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        AutomountConfigurationBuilder::build_drop_in(self)
+    }
+}
+
+impl<'a> DropInSection<'a> for PathConfigurationBuilder<'a> {
+    type Section = PathConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        self.build()
+    }
+}
+
+impl<'a> DropInSection<'a> for SliceConfigurationBuilder<'a> {
+    type Section = SliceConfiguration<'a>;
+
+    fn build_drop_in(self) -> Self::Section {
+        self.build()
+    }
+}
+
+// override file in /etc/systemd/system/<unit>.d/, like `systemctl edit` creates
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#Description
+pub struct DropInConfiguration<'a> {
+    pub sections: Vec<Box<dyn Display + 'a>>,
+}
+
+impl<'a> DropInConfiguration<'a> {
+    pub fn builder() -> DropInConfigurationBuilder<'a> {
+        DropInConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for DropInConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // sections without any setting, e.g. an empty [Install], render nothing
+        let sections: Vec<String> = self
+            .sections
+            .iter()
+            .map(ToString::to_string)
+            .filter(|section| !section.is_empty())
+            .collect();
+        write!(f, "{}", sections.join("\n"))
+    }
+}
+
+#[derive(Default)]
+pub struct DropInConfigurationBuilder<'a> {
+    pub sections: Vec<Box<dyn Display + 'a>>,
+}
+
+impl<'a> DropInConfigurationBuilder<'a> {
+    pub fn section<S: DropInSection<'a>>(mut self, section: S) -> Self {
+        self.sections.push(Box::new(section.build_drop_in()));
+        self
+    }
+
+    pub fn build(self) -> DropInConfiguration<'a> {
+        DropInConfiguration {
+            sections: self.sections,
+        }
+    }
+}
@@ -0,0 +1,357 @@
+use super::{write_escaped, write_list};
+use crate::{ErrorImpl, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Limit {
+    Value(u64),
+    Infinity,
+}
+
+impl Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Limit::Value(value) => write!(f, "{}", value),
+            Limit::Infinity => write!(f, "infinity"),
+        }
+    }
+}
+
+impl FromStr for Limit {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "infinity" {
+            return Ok(Limit::Infinity);
+        }
+        s.parse()
+            .map(Limit::Value)
+            .map_err(|_| ErrorImpl::InvalidValue(s.to_owned(), "resource limit").into())
+    }
+}
+
+// rendered as "soft:hard", or a single value if both are the same
+pub struct ResourceLimit {
+    pub soft: Limit,
+    pub hard: Limit,
+}
+
+impl ResourceLimit {
+    pub fn new(soft: Limit, hard: Limit) -> Self {
+        ResourceLimit { soft, hard }
+    }
+}
+
+impl Display for ResourceLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.soft == self.hard {
+            return write!(f, "{}", self.soft);
+        }
+        write!(f, "{}:{}", self.soft, self.hard)
+    }
+}
+
+impl FromStr for ResourceLimit {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (soft, hard) = s.split_once(':').unwrap_or((s, s));
+        Ok(ResourceLimit::new(soft.parse()?, hard.parse()?))
+    }
+}
+
+pub struct EnvironmentFile<'a> {
+    pub path: &'a str,
+    // "-" prefix, a missing file is not an error
+    pub optional: bool,
+}
+
+impl<'a> Display for EnvironmentFile<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.optional {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.path)
+    }
+}
+
+// StandardOutput= and StandardError= targets
+pub enum StandardOutput<'a> {
+    Inherit,
+    Null,
+    Tty,
+    Journal,
+    Kmsg,
+    JournalAndConsole,
+    KmsgAndConsole,
+    Socket,
+    File(&'a str),
+    Append(&'a str),
+    Truncate(&'a str),
+}
+
+impl<'a> Display for StandardOutput<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StandardOutput::Inherit => write!(f, "inherit"),
+            StandardOutput::Null => write!(f, "null"),
+            StandardOutput::Tty => write!(f, "tty"),
+            StandardOutput::Journal => write!(f, "journal"),
+            StandardOutput::Kmsg => write!(f, "kmsg"),
+            StandardOutput::JournalAndConsole => write!(f, "journal+console"),
+            StandardOutput::KmsgAndConsole => write!(f, "kmsg+console"),
+            StandardOutput::Socket => write!(f, "socket"),
+            StandardOutput::File(path) => write!(f, "file:{}", path),
+            StandardOutput::Append(path) => write!(f, "append:{}", path),
+            StandardOutput::Truncate(path) => write!(f, "truncate:{}", path),
+        }
+    }
+}
+
+// process execution options of the [Service] section
+// https://www.freedesktop.org/software/systemd/man/systemd.exec.html
+#[derive(Default)]
+pub struct ExecConfiguration<'a> {
+    pub limit_nofile: Option<ResourceLimit>,
+    pub limit_nproc: Option<ResourceLimit>,
+    pub limit_core: Option<ResourceLimit>,
+    pub nice: Option<i8>,
+    pub oom_score_adjust: Option<i16>,
+    pub umask: Option<u32>,
+    pub environment_files: Vec<EnvironmentFile<'a>>,
+    pub standard_output: Option<StandardOutput<'a>>,
+    pub standard_error: Option<StandardOutput<'a>>,
+    pub syslog_identifier: Option<&'a str>,
+    // (credential id, path)
+    pub load_credentials: Vec<(&'a str, &'a str)>,
+    // (credential id, value)
+    pub set_credentials: Vec<(&'a str, &'a str)>,
+    pub state_directory: Vec<&'a str>,
+    pub state_directory_mode: Option<u32>,
+    pub runtime_directory: Vec<&'a str>,
+    pub runtime_directory_mode: Option<u32>,
+    pub logs_directory: Vec<&'a str>,
+    pub logs_directory_mode: Option<u32>,
+    pub cache_directory: Vec<&'a str>,
+    pub cache_directory_mode: Option<u32>,
+}
+
+impl<'a> ExecConfiguration<'a> {
+    pub fn builder() -> ExecConfigurationBuilder<'a> {
+        ExecConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for ExecConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(limit_nofile) = &self.limit_nofile {
+            writeln!(f, "LimitNOFILE={}", limit_nofile)?;
+        }
+        if let Some(limit_nproc) = &self.limit_nproc {
+            writeln!(f, "LimitNPROC={}", limit_nproc)?;
+        }
+        if let Some(limit_core) = &self.limit_core {
+            writeln!(f, "LimitCORE={}", limit_core)?;
+        }
+        if let Some(nice) = self.nice {
+            writeln!(f, "Nice={}", nice)?;
+        }
+        if let Some(oom_score_adjust) = self.oom_score_adjust {
+            writeln!(f, "OOMScoreAdjust={}", oom_score_adjust)?;
+        }
+        if let Some(umask) = self.umask {
+            writeln!(f, "UMask={:04o}", umask)?;
+        }
+        for environment_file in self.environment_files.iter() {
+            writeln!(f, "EnvironmentFile={}", environment_file)?;
+        }
+        if let Some(standard_output) = &self.standard_output {
+            writeln!(f, "StandardOutput={}", standard_output)?;
+        }
+        if let Some(standard_error) = &self.standard_error {
+            writeln!(f, "StandardError={}", standard_error)?;
+        }
+        if let Some(syslog_identifier) = self.syslog_identifier {
+            writeln!(f, "SyslogIdentifier={}", syslog_identifier)?;
+        }
+        for (id, path) in self.load_credentials.iter() {
+            writeln!(f, "LoadCredential={}:{}", id, path)?;
+        }
+        // the value is free-form text that systemd C-unescapes, unlike the path of LoadCredential=
+        for (id, value) in self.set_credentials.iter() {
+            write!(f, "SetCredential={}:", id)?;
+            write_escaped(f, value, false)?;
+            writeln!(f)?;
+        }
+        let directories = [
+            ("State", &self.state_directory, self.state_directory_mode),
+            (
+                "Runtime",
+                &self.runtime_directory,
+                self.runtime_directory_mode,
+            ),
+            ("Logs", &self.logs_directory, self.logs_directory_mode),
+            ("Cache", &self.cache_directory, self.cache_directory_mode),
+        ];
+        for (kind, directory, mode) in directories {
+            write_list(f, &format!("{}Directory", kind), directory)?;
+            if let Some(mode) = mode {
+                writeln!(f, "{}DirectoryMode={:04o}", kind, mode)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct ExecConfigurationBuilder<'a> {
+    pub limit_nofile: Option<ResourceLimit>,
+    pub limit_nproc: Option<ResourceLimit>,
+    pub limit_core: Option<ResourceLimit>,
+    pub nice: Option<i8>,
+    pub oom_score_adjust: Option<i16>,
+    pub umask: Option<u32>,
+    pub environment_files: Vec<EnvironmentFile<'a>>,
+    pub standard_output: Option<StandardOutput<'a>>,
+    pub standard_error: Option<StandardOutput<'a>>,
+    pub syslog_identifier: Option<&'a str>,
+    pub load_credentials: Vec<(&'a str, &'a str)>,
+    pub set_credentials: Vec<(&'a str, &'a str)>,
+    pub state_directory: Vec<&'a str>,
+    pub state_directory_mode: Option<u32>,
+    pub runtime_directory: Vec<&'a str>,
+    pub runtime_directory_mode: Option<u32>,
+    pub logs_directory: Vec<&'a str>,
+    pub logs_directory_mode: Option<u32>,
+    pub cache_directory: Vec<&'a str>,
+    pub cache_directory_mode: Option<u32>,
+}
+
+impl<'a> ExecConfigurationBuilder<'a> {
+    pub fn limit_nofile(mut self, soft: Limit, hard: Limit) -> Self {
+        self.limit_nofile = Some(ResourceLimit::new(soft, hard));
+        self
+    }
+
+    pub fn limit_nproc(mut self, soft: Limit, hard: Limit) -> Self {
+        self.limit_nproc = Some(ResourceLimit::new(soft, hard));
+        self
+    }
+
+    pub fn limit_core(mut self, soft: Limit, hard: Limit) -> Self {
+        self.limit_core = Some(ResourceLimit::new(soft, hard));
+        self
+    }
+
+    pub fn nice(mut self, nice: i8) -> Self {
+        self.nice = Some(nice);
+        self
+    }
+
+    pub fn oom_score_adjust(mut self, oom_score_adjust: i16) -> Self {
+        self.oom_score_adjust = Some(oom_score_adjust);
+        self
+    }
+
+    pub fn umask(mut self, umask: u32) -> Self {
+        self.umask = Some(umask);
+        self
+    }
+
+    pub fn environment_file(mut self, path: &'a str, optional: bool) -> Self {
+        self.environment_files
+            .push(EnvironmentFile { path, optional });
+        self
+    }
+
+    pub fn standard_output(mut self, standard_output: StandardOutput<'a>) -> Self {
+        self.standard_output = Some(standard_output);
+        self
+    }
+
+    pub fn standard_error(mut self, standard_error: StandardOutput<'a>) -> Self {
+        self.standard_error = Some(standard_error);
+        self
+    }
+
+    pub fn syslog_identifier(mut self, syslog_identifier: &'a str) -> Self {
+        self.syslog_identifier = Some(syslog_identifier);
+        self
+    }
+
+    pub fn load_credential(mut self, id: &'a str, path: &'a str) -> Self {
+        self.load_credentials.push((id, path));
+        self
+    }
+
+    pub fn set_credential(mut self, id: &'a str, value: &'a str) -> Self {
+        self.set_credentials.push((id, value));
+        self
+    }
+
+    pub fn state_directory(mut self, state_directory: &'a str) -> Self {
+        self.state_directory.push(state_directory);
+        self
+    }
+
+    pub fn state_directory_mode(mut self, state_directory_mode: u32) -> Self {
+        self.state_directory_mode = Some(state_directory_mode);
+        self
+    }
+
+    pub fn runtime_directory(mut self, runtime_directory: &'a str) -> Self {
+        self.runtime_directory.push(runtime_directory);
+        self
+    }
+
+    pub fn runtime_directory_mode(mut self, runtime_directory_mode: u32) -> Self {
+        self.runtime_directory_mode = Some(runtime_directory_mode);
+        self
+    }
+
+    pub fn logs_directory(mut self, logs_directory: &'a str) -> Self {
+        self.logs_directory.push(logs_directory);
+        self
+    }
+
+    pub fn logs_directory_mode(mut self, logs_directory_mode: u32) -> Self {
+        self.logs_directory_mode = Some(logs_directory_mode);
+        self
+    }
+
+    pub fn cache_directory(mut self, cache_directory: &'a str) -> Self {
+        self.cache_directory.push(cache_directory);
+        self
+    }
+
+    pub fn cache_directory_mode(mut self, cache_directory_mode: u32) -> Self {
+        self.cache_directory_mode = Some(cache_directory_mode);
+        self
+    }
+
+    pub fn build(self) -> ExecConfiguration<'a> {
+        ExecConfiguration {
+            limit_nofile: self.limit_nofile,
+            limit_nproc: self.limit_nproc,
+            limit_core: self.limit_core,
+            nice: self.nice,
+            oom_score_adjust: self.oom_score_adjust,
+            umask: self.umask,
+            environment_files: self.environment_files,
+            standard_output: self.standard_output,
+            standard_error: self.standard_error,
+            syslog_identifier: self.syslog_identifier,
+            load_credentials: self.load_credentials,
+            set_credentials: self.set_credentials,
+            state_directory: self.state_directory,
+            state_directory_mode: self.state_directory_mode,
+            runtime_directory: self.runtime_directory,
+            runtime_directory_mode: self.runtime_directory_mode,
+            logs_directory: self.logs_directory,
+            logs_directory_mode: self.logs_directory_mode,
+            cache_directory: self.cache_directory,
+            cache_directory_mode: self.cache_directory_mode,
+        }
+    }
+}
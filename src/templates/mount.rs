@@ -0,0 +1,288 @@
+use super::{
+    write_resets, yes_no, ResourceControlConfiguration, ResourceControlConfigurationBuilder,
+};
+use crate::{escape_unit_path, ErrorImpl, Result, UnitName, UnitType};
+use std::fmt::Display;
+
+// systemd refuses relative mount points, escaping one would still yield a plausible unit name
+fn is_absolute(where_: &str) -> bool {
+    where_.starts_with('/')
+}
+
+fn mount_point_unit_name(where_: &str, unit_type: UnitType) -> Result<UnitName> {
+    if !is_absolute(where_) {
+        return Err(ErrorImpl::InvalidPath(where_.to_owned()).into());
+    }
+    format!("{}.{}", escape_unit_path(where_)?, unit_type).parse()
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.mount.html#Options
+pub struct MountConfiguration<'a> {
+    pub what: &'a str,
+    pub where_: &'a str,
+    pub ty: Option<&'a str>,
+    pub options: Vec<&'a str>,
+    pub sloppy_options: Option<bool>,
+    pub lazy_unmount: Option<bool>,
+    pub read_write_only: Option<bool>,
+    pub force_unmount: Option<bool>,
+    pub directory_mode: Option<u32>,
+    pub timeout_sec: Option<&'a str>,
+    pub resource_control: ResourceControlConfiguration<'a>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> MountConfiguration<'a> {
+    pub fn builder() -> MountConfigurationBuilder<'a> {
+        MountConfigurationBuilder::default()
+    }
+
+    // mount units must be named after the escaped mount point, e.g. "/home/foo" => "home-foo.mount"
+    pub fn unit_name(&self) -> Result<UnitName> {
+        mount_point_unit_name(self.where_, UnitType::Mount)
+    }
+}
+
+impl<'a> Display for MountConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Mount]")?;
+        write_resets(f, &self.resets)?;
+        // only empty in drop-ins, which cannot change them
+        if !self.what.is_empty() {
+            writeln!(f, "What={}", self.what)?;
+        }
+        if !self.where_.is_empty() {
+            writeln!(f, "Where={}", self.where_)?;
+        }
+        if let Some(ty) = self.ty {
+            writeln!(f, "Type={}", ty)?;
+        }
+        if !self.options.is_empty() {
+            writeln!(f, "Options={}", self.options.join(","))?;
+        }
+        if let Some(sloppy_options) = self.sloppy_options {
+            writeln!(f, "SloppyOptions={}", yes_no(sloppy_options))?;
+        }
+        if let Some(lazy_unmount) = self.lazy_unmount {
+            writeln!(f, "LazyUnmount={}", yes_no(lazy_unmount))?;
+        }
+        if let Some(read_write_only) = self.read_write_only {
+            writeln!(f, "ReadWriteOnly={}", yes_no(read_write_only))?;
+        }
+        if let Some(force_unmount) = self.force_unmount {
+            writeln!(f, "ForceUnmount={}", yes_no(force_unmount))?;
+        }
+        if let Some(directory_mode) = self.directory_mode {
+            writeln!(f, "DirectoryMode={:04o}", directory_mode)?;
+        }
+        if let Some(timeout_sec) = self.timeout_sec {
+            writeln!(f, "TimeoutSec={}", timeout_sec)?;
+        }
+        write!(f, "{}", self.resource_control)
+    }
+}
+
+#[derive(Default)]
+pub struct MountConfigurationBuilder<'a> {
+    pub what: Option<&'a str>,
+    pub where_: Option<&'a str>,
+    pub ty: Option<&'a str>,
+    pub options: Vec<&'a str>,
+    pub sloppy_options: Option<bool>,
+    pub lazy_unmount: Option<bool>,
+    pub read_write_only: Option<bool>,
+    pub force_unmount: Option<bool>,
+    pub directory_mode: Option<u32>,
+    pub timeout_sec: Option<&'a str>,
+    pub resource_control: ResourceControlConfigurationBuilder<'a>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> MountConfigurationBuilder<'a> {
+    pub fn what(mut self, what: &'a str) -> Self {
+        self.what = Some(what);
+        self
+    }
+
+    pub fn where_(mut self, where_: &'a str) -> Self {
+        self.where_ = Some(where_);
+        self
+    }
+
+    pub fn ty(mut self, ty: &'a str) -> Self {
+        self.ty = Some(ty);
+        self
+    }
+
+    pub fn option(mut self, option: &'a str) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    pub fn sloppy_options(mut self, sloppy_options: bool) -> Self {
+        self.sloppy_options = Some(sloppy_options);
+        self
+    }
+
+    pub fn lazy_unmount(mut self, lazy_unmount: bool) -> Self {
+        self.lazy_unmount = Some(lazy_unmount);
+        self
+    }
+
+    pub fn read_write_only(mut self, read_write_only: bool) -> Self {
+        self.read_write_only = Some(read_write_only);
+        self
+    }
+
+    pub fn force_unmount(mut self, force_unmount: bool) -> Self {
+        self.force_unmount = Some(force_unmount);
+        self
+    }
+
+    pub fn directory_mode(mut self, directory_mode: u32) -> Self {
+        self.directory_mode = Some(directory_mode);
+        self
+    }
+
+    pub fn timeout_sec(mut self, timeout_sec: &'a str) -> Self {
+        self.timeout_sec = Some(timeout_sec);
+        self
+    }
+
+    pub fn resource_control(
+        mut self,
+        resource_control: ResourceControlConfigurationBuilder<'a>,
+    ) -> Self {
+        self.resource_control = resource_control;
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    pub fn build(self) -> MountConfiguration<'a> {
+        assert!(self.what.is_some(), "what undefined");
+        assert!(self.where_.is_some(), "where undefined");
+        assert!(
+            self.where_.is_some_and(is_absolute),
+            "where must be an absolute path"
+        );
+        self.build_drop_in()
+    }
+
+    // partial [Mount] section of a drop-in, What= and Where= may be left out
+    pub fn build_drop_in(self) -> MountConfiguration<'a> {
+        MountConfiguration {
+            what: self.what.unwrap_or_default(),
+            where_: self.where_.unwrap_or_default(),
+            ty: self.ty,
+            options: self.options,
+            sloppy_options: self.sloppy_options,
+            lazy_unmount: self.lazy_unmount,
+            read_write_only: self.read_write_only,
+            force_unmount: self.force_unmount,
+            directory_mode: self.directory_mode,
+            timeout_sec: self.timeout_sec,
+            resource_control: self.resource_control.build(),
+            resets: self.resets,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.automount.html#Options
+pub struct AutomountConfiguration<'a> {
+    pub where_: &'a str,
+    pub extra_options: Vec<&'a str>,
+    pub directory_mode: Option<u32>,
+    pub timeout_idle_sec: Option<&'a str>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> AutomountConfiguration<'a> {
+    pub fn builder() -> AutomountConfigurationBuilder<'a> {
+        AutomountConfigurationBuilder::default()
+    }
+
+    // automount units must be named after the escaped mount point, e.g. "/home/foo" => "home-foo.automount"
+    pub fn unit_name(&self) -> Result<UnitName> {
+        mount_point_unit_name(self.where_, UnitType::Automount)
+    }
+}
+
+impl<'a> Display for AutomountConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Automount]")?;
+        write_resets(f, &self.resets)?;
+        if !self.where_.is_empty() {
+            writeln!(f, "Where={}", self.where_)?;
+        }
+        if !self.extra_options.is_empty() {
+            writeln!(f, "ExtraOptions={}", self.extra_options.join(","))?;
+        }
+        if let Some(directory_mode) = self.directory_mode {
+            writeln!(f, "DirectoryMode={:04o}", directory_mode)?;
+        }
+        if let Some(timeout_idle_sec) = self.timeout_idle_sec {
+            writeln!(f, "TimeoutIdleSec={}", timeout_idle_sec)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct AutomountConfigurationBuilder<'a> {
+    pub where_: Option<&'a str>,
+    pub extra_options: Vec<&'a str>,
+    pub directory_mode: Option<u32>,
+    pub timeout_idle_sec: Option<&'a str>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> AutomountConfigurationBuilder<'a> {
+    pub fn where_(mut self, where_: &'a str) -> Self {
+        self.where_ = Some(where_);
+        self
+    }
+
+    pub fn extra_option(mut self, extra_option: &'a str) -> Self {
+        self.extra_options.push(extra_option);
+        self
+    }
+
+    pub fn directory_mode(mut self, directory_mode: u32) -> Self {
+        self.directory_mode = Some(directory_mode);
+        self
+    }
+
+    pub fn timeout_idle_sec(mut self, timeout_idle_sec: &'a str) -> Self {
+        self.timeout_idle_sec = Some(timeout_idle_sec);
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    pub fn build(self) -> AutomountConfiguration<'a> {
+        assert!(self.where_.is_some(), "where undefined");
+        assert!(
+            self.where_.is_some_and(is_absolute),
+            "where must be an absolute path"
+        );
+        self.build_drop_in()
+    }
+
+    // partial [Automount] section of a drop-in, Where= may be left out
+    pub fn build_drop_in(self) -> AutomountConfiguration<'a> {
+        AutomountConfiguration {
+            where_: self.where_.unwrap_or_default(),
+            extra_options: self.extra_options,
+            directory_mode: self.directory_mode,
+            timeout_idle_sec: self.timeout_idle_sec,
+            resets: self.resets,
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use super::{write_resets, yes_no};
+use std::fmt::Display;
+
+pub enum PathCondition<'a> {
+    PathExists(&'a str),
+    PathExistsGlob(&'a str),
+    PathChanged(&'a str),
+    PathModified(&'a str),
+    DirectoryNotEmpty(&'a str),
+}
+
+impl<'a> Display for PathCondition<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathCondition::PathExists(path) => write!(f, "PathExists={}", path),
+            PathCondition::PathExistsGlob(glob) => write!(f, "PathExistsGlob={}", glob),
+            PathCondition::PathChanged(path) => write!(f, "PathChanged={}", path),
+            PathCondition::PathModified(path) => write!(f, "PathModified={}", path),
+            PathCondition::DirectoryNotEmpty(path) => write!(f, "DirectoryNotEmpty={}", path),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.path.html#Options
+#[derive(Default)]
+pub struct PathConfiguration<'a> {
+    pub conditions: Vec<PathCondition<'a>>,
+    // unit to activate, defaults to the service with the same name as the path unit
+    pub unit: Option<&'a str>,
+    pub make_directory: Option<bool>,
+    pub directory_mode: Option<u32>,
+    pub trigger_limit_interval_sec: Option<&'a str>,
+    pub trigger_limit_burst: Option<u32>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> PathConfiguration<'a> {
+    pub fn builder() -> PathConfigurationBuilder<'a> {
+        PathConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for PathConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Path]")?;
+        write_resets(f, &self.resets)?;
+        for condition in self.conditions.iter() {
+            writeln!(f, "{}", condition)?;
+        }
+        if let Some(unit) = self.unit {
+            writeln!(f, "Unit={}", unit)?;
+        }
+        if let Some(make_directory) = self.make_directory {
+            writeln!(f, "MakeDirectory={}", yes_no(make_directory))?;
+        }
+        if let Some(directory_mode) = self.directory_mode {
+            writeln!(f, "DirectoryMode={:04o}", directory_mode)?;
+        }
+        if let Some(trigger_limit_interval_sec) = self.trigger_limit_interval_sec {
+            writeln!(f, "TriggerLimitIntervalSec={}", trigger_limit_interval_sec)?;
+        }
+        if let Some(trigger_limit_burst) = self.trigger_limit_burst {
+            writeln!(f, "TriggerLimitBurst={}", trigger_limit_burst)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct PathConfigurationBuilder<'a> {
+    pub conditions: Vec<PathCondition<'a>>,
+    pub unit: Option<&'a str>,
+    pub make_directory: Option<bool>,
+    pub directory_mode: Option<u32>,
+    pub trigger_limit_interval_sec: Option<&'a str>,
+    pub trigger_limit_burst: Option<u32>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> PathConfigurationBuilder<'a> {
+    pub fn condition(mut self, condition: PathCondition<'a>) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    pub fn unit(mut self, unit: &'a str) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn make_directory(mut self, make_directory: bool) -> Self {
+        self.make_directory = Some(make_directory);
+        self
+    }
+
+    pub fn directory_mode(mut self, directory_mode: u32) -> Self {
+        self.directory_mode = Some(directory_mode);
+        self
+    }
+
+    pub fn trigger_limit_interval_sec(mut self, trigger_limit_interval_sec: &'a str) -> Self {
+        self.trigger_limit_interval_sec = Some(trigger_limit_interval_sec);
+        self
+    }
+
+    pub fn trigger_limit_burst(mut self, trigger_limit_burst: u32) -> Self {
+        self.trigger_limit_burst = Some(trigger_limit_burst);
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    pub fn build(self) -> PathConfiguration<'a> {
+        PathConfiguration {
+            conditions: self.conditions,
+            unit: self.unit,
+            make_directory: self.make_directory,
+            directory_mode: self.directory_mode,
+            trigger_limit_interval_sec: self.trigger_limit_interval_sec,
+            trigger_limit_burst: self.trigger_limit_burst,
+            resets: self.resets,
+        }
+    }
+}
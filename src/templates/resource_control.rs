@@ -0,0 +1,332 @@
+use super::{write_list, yes_no};
+use crate::{ErrorImpl, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+// memory and bandwidth sizes, rendered with the largest exact K/M/G/T (base 1024) suffix
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ByteSize {
+    Bytes(u64),
+    Infinity,
+}
+
+// sizes that do not fit into u64 bytes are unlimited anyway
+impl ByteSize {
+    fn scaled(value: u64, factor: u64) -> Self {
+        match value.checked_mul(factor) {
+            Some(bytes) => ByteSize::Bytes(bytes),
+            None => ByteSize::Infinity,
+        }
+    }
+
+    pub fn kibibytes(kibibytes: u64) -> Self {
+        ByteSize::scaled(kibibytes, 1 << 10)
+    }
+
+    pub fn mebibytes(mebibytes: u64) -> Self {
+        ByteSize::scaled(mebibytes, 1 << 20)
+    }
+
+    pub fn gibibytes(gibibytes: u64) -> Self {
+        ByteSize::scaled(gibibytes, 1 << 30)
+    }
+
+    pub fn tebibytes(tebibytes: u64) -> Self {
+        ByteSize::scaled(tebibytes, 1 << 40)
+    }
+}
+
+impl Display for ByteSize {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let bytes = match self {
+            ByteSize::Bytes(bytes) => *bytes,
+            ByteSize::Infinity => return write!(f, "infinity"),
+        };
+        for (suffix, shift) in [("T", 40), ("G", 30), ("M", 20), ("K", 10)] {
+            let factor = 1u64 << shift;
+            if bytes >= factor && bytes % factor == 0 {
+                return write!(f, "{}{}", bytes >> shift, suffix);
+            }
+        }
+        write!(f, "{}", bytes)
+    }
+}
+
+// bytes with an optional K/M/G/T suffix, percentages of the physical memory are not supported
+impl FromStr for ByteSize {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        if s == "infinity" {
+            return Ok(ByteSize::Infinity);
+        }
+        let (digits, shift) = match s.char_indices().last() {
+            Some((i, 'K')) => (&s[..i], 10),
+            Some((i, 'M')) => (&s[..i], 20),
+            Some((i, 'G')) => (&s[..i], 30),
+            Some((i, 'T')) => (&s[..i], 40),
+            _ => (s, 0),
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| ErrorImpl::InvalidValue(s.to_owned(), "byte size"))?;
+        Ok(ByteSize::scaled(value, 1 << shift))
+    }
+}
+
+pub enum TasksMax {
+    Count(u64),
+    // percentage of the system wide limit
+    Percent(u32),
+    Infinity,
+}
+
+impl Display for TasksMax {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TasksMax::Count(count) => write!(f, "{}", count),
+            TasksMax::Percent(percent) => write!(f, "{}%", percent),
+            TasksMax::Infinity => write!(f, "infinity"),
+        }
+    }
+}
+
+impl FromStr for TasksMax {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let invalid = || ErrorImpl::InvalidValue(s.to_owned(), "TasksMax=");
+        if s == "infinity" {
+            return Ok(TasksMax::Infinity);
+        }
+        if let Some(percent) = s.strip_suffix('%') {
+            return Ok(TasksMax::Percent(percent.parse().map_err(|_| invalid())?));
+        }
+        Ok(TasksMax::Count(s.parse().map_err(|_| invalid())?))
+    }
+}
+
+// resource control options shared by service, socket, slice, scope, mount and swap units
+// https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+#[derive(Default)]
+pub struct ResourceControlConfiguration<'a> {
+    pub slice: Option<&'a str>,
+    pub cpu_weight: Option<u32>,
+    // percentage of one cpu, may exceed 100
+    pub cpu_quota: Option<u32>,
+    // cpu indices or ranges, e.g. "0-3 8"
+    pub allowed_cpus: Option<&'a str>,
+    pub memory_min: Option<ByteSize>,
+    pub memory_low: Option<ByteSize>,
+    pub memory_high: Option<ByteSize>,
+    pub memory_max: Option<ByteSize>,
+    pub memory_swap_max: Option<ByteSize>,
+    pub tasks_max: Option<TasksMax>,
+    pub io_weight: Option<u32>,
+    // (device, weight)
+    pub io_device_weight: Vec<(&'a str, u32)>,
+    // (device, bytes per second)
+    pub io_read_bandwidth_max: Vec<(&'a str, ByteSize)>,
+    pub io_write_bandwidth_max: Vec<(&'a str, ByteSize)>,
+    pub ip_address_allow: Vec<&'a str>,
+    pub ip_address_deny: Vec<&'a str>,
+    pub delegate: Option<bool>,
+}
+
+impl<'a> ResourceControlConfiguration<'a> {
+    pub fn builder() -> ResourceControlConfigurationBuilder<'a> {
+        ResourceControlConfigurationBuilder::default()
+    }
+
+    // a slice unit cannot be placed in another slice, so [Slice] leaves out Slice=
+    pub(super) fn write_settings(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+        with_slice: bool,
+    ) -> std::fmt::Result {
+        if let Some(slice) = self.slice.filter(|_| with_slice) {
+            writeln!(f, "Slice={}", slice)?;
+        }
+        if let Some(cpu_weight) = self.cpu_weight {
+            writeln!(f, "CPUWeight={}", cpu_weight)?;
+        }
+        if let Some(cpu_quota) = self.cpu_quota {
+            writeln!(f, "CPUQuota={}%", cpu_quota)?;
+        }
+        if let Some(allowed_cpus) = self.allowed_cpus {
+            writeln!(f, "AllowedCPUs={}", allowed_cpus)?;
+        }
+        if let Some(memory_min) = self.memory_min {
+            writeln!(f, "MemoryMin={}", memory_min)?;
+        }
+        if let Some(memory_low) = self.memory_low {
+            writeln!(f, "MemoryLow={}", memory_low)?;
+        }
+        if let Some(memory_high) = self.memory_high {
+            writeln!(f, "MemoryHigh={}", memory_high)?;
+        }
+        if let Some(memory_max) = self.memory_max {
+            writeln!(f, "MemoryMax={}", memory_max)?;
+        }
+        if let Some(memory_swap_max) = self.memory_swap_max {
+            writeln!(f, "MemorySwapMax={}", memory_swap_max)?;
+        }
+        if let Some(tasks_max) = &self.tasks_max {
+            writeln!(f, "TasksMax={}", tasks_max)?;
+        }
+        if let Some(io_weight) = self.io_weight {
+            writeln!(f, "IOWeight={}", io_weight)?;
+        }
+        for (device, weight) in self.io_device_weight.iter() {
+            writeln!(f, "IODeviceWeight={} {}", device, weight)?;
+        }
+        for (device, bandwidth) in self.io_read_bandwidth_max.iter() {
+            writeln!(f, "IOReadBandwidthMax={} {}", device, bandwidth)?;
+        }
+        for (device, bandwidth) in self.io_write_bandwidth_max.iter() {
+            writeln!(f, "IOWriteBandwidthMax={} {}", device, bandwidth)?;
+        }
+        write_list(f, "IPAddressAllow", &self.ip_address_allow)?;
+        write_list(f, "IPAddressDeny", &self.ip_address_deny)?;
+        if let Some(delegate) = self.delegate {
+            writeln!(f, "Delegate={}", yes_no(delegate))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Display for ResourceControlConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_settings(f, true)
+    }
+}
+
+#[derive(Default)]
+pub struct ResourceControlConfigurationBuilder<'a> {
+    pub slice: Option<&'a str>,
+    pub cpu_weight: Option<u32>,
+    pub cpu_quota: Option<u32>,
+    pub allowed_cpus: Option<&'a str>,
+    pub memory_min: Option<ByteSize>,
+    pub memory_low: Option<ByteSize>,
+    pub memory_high: Option<ByteSize>,
+    pub memory_max: Option<ByteSize>,
+    pub memory_swap_max: Option<ByteSize>,
+    pub tasks_max: Option<TasksMax>,
+    pub io_weight: Option<u32>,
+    pub io_device_weight: Vec<(&'a str, u32)>,
+    pub io_read_bandwidth_max: Vec<(&'a str, ByteSize)>,
+    pub io_write_bandwidth_max: Vec<(&'a str, ByteSize)>,
+    pub ip_address_allow: Vec<&'a str>,
+    pub ip_address_deny: Vec<&'a str>,
+    pub delegate: Option<bool>,
+}
+
+impl<'a> ResourceControlConfigurationBuilder<'a> {
+    pub fn slice(mut self, slice: &'a str) -> Self {
+        self.slice = Some(slice);
+        self
+    }
+
+    pub fn cpu_weight(mut self, cpu_weight: u32) -> Self {
+        self.cpu_weight = Some(cpu_weight);
+        self
+    }
+
+    pub fn cpu_quota(mut self, cpu_quota: u32) -> Self {
+        self.cpu_quota = Some(cpu_quota);
+        self
+    }
+
+    pub fn allowed_cpus(mut self, allowed_cpus: &'a str) -> Self {
+        self.allowed_cpus = Some(allowed_cpus);
+        self
+    }
+
+    pub fn memory_min(mut self, memory_min: ByteSize) -> Self {
+        self.memory_min = Some(memory_min);
+        self
+    }
+
+    pub fn memory_low(mut self, memory_low: ByteSize) -> Self {
+        self.memory_low = Some(memory_low);
+        self
+    }
+
+    pub fn memory_high(mut self, memory_high: ByteSize) -> Self {
+        self.memory_high = Some(memory_high);
+        self
+    }
+
+    pub fn memory_max(mut self, memory_max: ByteSize) -> Self {
+        self.memory_max = Some(memory_max);
+        self
+    }
+
+    pub fn memory_swap_max(mut self, memory_swap_max: ByteSize) -> Self {
+        self.memory_swap_max = Some(memory_swap_max);
+        self
+    }
+
+    pub fn tasks_max(mut self, tasks_max: TasksMax) -> Self {
+        self.tasks_max = Some(tasks_max);
+        self
+    }
+
+    pub fn io_weight(mut self, io_weight: u32) -> Self {
+        self.io_weight = Some(io_weight);
+        self
+    }
+
+    pub fn io_device_weight(mut self, device: &'a str, weight: u32) -> Self {
+        self.io_device_weight.push((device, weight));
+        self
+    }
+
+    pub fn io_read_bandwidth_max(mut self, device: &'a str, bandwidth: ByteSize) -> Self {
+        self.io_read_bandwidth_max.push((device, bandwidth));
+        self
+    }
+
+    pub fn io_write_bandwidth_max(mut self, device: &'a str, bandwidth: ByteSize) -> Self {
+        self.io_write_bandwidth_max.push((device, bandwidth));
+        self
+    }
+
+    pub fn ip_address_allow(mut self, address: &'a str) -> Self {
+        self.ip_address_allow.push(address);
+        self
+    }
+
+    pub fn ip_address_deny(mut self, address: &'a str) -> Self {
+        self.ip_address_deny.push(address);
+        self
+    }
+
+    pub fn delegate(mut self, delegate: bool) -> Self {
+        self.delegate = Some(delegate);
+        self
+    }
+
+    pub fn build(self) -> ResourceControlConfiguration<'a> {
+        ResourceControlConfiguration {
+            slice: self.slice,
+            cpu_weight: self.cpu_weight,
+            cpu_quota: self.cpu_quota,
+            allowed_cpus: self.allowed_cpus,
+            memory_min: self.memory_min,
+            memory_low: self.memory_low,
+            memory_high: self.memory_high,
+            memory_max: self.memory_max,
+            memory_swap_max: self.memory_swap_max,
+            tasks_max: self.tasks_max,
+            io_weight: self.io_weight,
+            io_device_weight: self.io_device_weight,
+            io_read_bandwidth_max: self.io_read_bandwidth_max,
+            io_write_bandwidth_max: self.io_write_bandwidth_max,
+            ip_address_allow: self.ip_address_allow,
+            ip_address_deny: self.ip_address_deny,
+            delegate: self.delegate,
+        }
+    }
+}
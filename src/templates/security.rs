@@ -0,0 +1,339 @@
+use super::{write_each, write_list, yes_no};
+use crate::{parser::parse_boolean, ErrorImpl, Result};
+use std::fmt::Display;
+use std::str::FromStr;
+
+pub enum ProtectSystem {
+    Yes,
+    No,
+    Full,
+    Strict,
+}
+
+impl Display for ProtectSystem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let protect = match self {
+            ProtectSystem::Yes => "yes",
+            ProtectSystem::No => "no",
+            ProtectSystem::Full => "full",
+            ProtectSystem::Strict => "strict",
+        };
+        write!(f, "{}", protect)
+    }
+}
+
+impl FromStr for ProtectSystem {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = match (s, parse_boolean(s)) {
+            (_, Some(true)) => ProtectSystem::Yes,
+            (_, Some(false)) => ProtectSystem::No,
+            ("full", None) => ProtectSystem::Full,
+            ("strict", None) => ProtectSystem::Strict,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "ProtectSystem=").into()),
+        };
+        Ok(value)
+    }
+}
+
+pub enum ProtectHome {
+    Yes,
+    No,
+    ReadOnly,
+    Tmpfs,
+}
+
+impl Display for ProtectHome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let protect = match self {
+            ProtectHome::Yes => "yes",
+            ProtectHome::No => "no",
+            ProtectHome::ReadOnly => "read-only",
+            ProtectHome::Tmpfs => "tmpfs",
+        };
+        write!(f, "{}", protect)
+    }
+}
+
+impl FromStr for ProtectHome {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = match (s, parse_boolean(s)) {
+            (_, Some(true)) => ProtectHome::Yes,
+            (_, Some(false)) => ProtectHome::No,
+            ("read-only", None) => ProtectHome::ReadOnly,
+            ("tmpfs", None) => ProtectHome::Tmpfs,
+            _ => return Err(ErrorImpl::InvalidValue(s.to_owned(), "ProtectHome=").into()),
+        };
+        Ok(value)
+    }
+}
+
+pub enum AddressFamily<'a> {
+    Unix,
+    Inet,
+    Inet6,
+    Netlink,
+    Packet,
+    // any other AF_* name, e.g. "AF_BLUETOOTH"
+    Other(&'a str),
+}
+
+impl<'a> Display for AddressFamily<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let family = match self {
+            AddressFamily::Unix => "AF_UNIX",
+            AddressFamily::Inet => "AF_INET",
+            AddressFamily::Inet6 => "AF_INET6",
+            AddressFamily::Netlink => "AF_NETLINK",
+            AddressFamily::Packet => "AF_PACKET",
+            AddressFamily::Other(other) => other,
+        };
+        write!(f, "{}", family)
+    }
+}
+
+impl<'a> From<&'a str> for AddressFamily<'a> {
+    fn from(family: &'a str) -> Self {
+        match family {
+            "AF_UNIX" => AddressFamily::Unix,
+            "AF_INET" => AddressFamily::Inet,
+            "AF_INET6" => AddressFamily::Inet6,
+            "AF_NETLINK" => AddressFamily::Netlink,
+            "AF_PACKET" => AddressFamily::Packet,
+            other => AddressFamily::Other(other),
+        }
+    }
+}
+
+// sandboxing options of the [Service] section
+// https://www.freedesktop.org/software/systemd/man/systemd.exec.html#Security
+// https://www.freedesktop.org/software/systemd/man/systemd.exec.html#Sandboxing
+#[derive(Default)]
+pub struct SecurityConfiguration<'a> {
+    pub protect_system: Option<ProtectSystem>,
+    pub protect_home: Option<ProtectHome>,
+    pub private_tmp: Option<bool>,
+    pub private_devices: Option<bool>,
+    pub private_network: Option<bool>,
+    pub no_new_privileges: Option<bool>,
+    pub dynamic_user: Option<bool>,
+    // capability names, e.g. "CAP_NET_BIND_SERVICE", or "~CAP_SYS_ADMIN" to drop one
+    pub capability_bounding_set: Vec<&'a str>,
+    pub ambient_capabilities: Vec<&'a str>,
+    pub read_only_paths: Vec<&'a str>,
+    pub read_write_paths: Vec<&'a str>,
+    pub inaccessible_paths: Vec<&'a str>,
+    // AddressFamily::Other("~AF_PACKET") denies a family
+    pub restrict_address_families: Vec<AddressFamily<'a>>,
+    // system calls or groups, e.g. "@system-service", "~@privileged"
+    // the first entry decides between allow and deny list, so each entry gets its own line
+    pub system_call_filter: Vec<&'a str>,
+    pub protect_kernel_tunables: Option<bool>,
+    pub protect_kernel_modules: Option<bool>,
+    pub protect_kernel_logs: Option<bool>,
+    pub memory_deny_write_execute: Option<bool>,
+}
+
+impl<'a> SecurityConfiguration<'a> {
+    pub fn builder() -> SecurityConfigurationBuilder<'a> {
+        SecurityConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for SecurityConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(protect_system) = &self.protect_system {
+            writeln!(f, "ProtectSystem={}", protect_system)?;
+        }
+        if let Some(protect_home) = &self.protect_home {
+            writeln!(f, "ProtectHome={}", protect_home)?;
+        }
+        if let Some(private_tmp) = self.private_tmp {
+            writeln!(f, "PrivateTmp={}", yes_no(private_tmp))?;
+        }
+        if let Some(private_devices) = self.private_devices {
+            writeln!(f, "PrivateDevices={}", yes_no(private_devices))?;
+        }
+        if let Some(private_network) = self.private_network {
+            writeln!(f, "PrivateNetwork={}", yes_no(private_network))?;
+        }
+        if let Some(no_new_privileges) = self.no_new_privileges {
+            writeln!(f, "NoNewPrivileges={}", yes_no(no_new_privileges))?;
+        }
+        if let Some(dynamic_user) = self.dynamic_user {
+            writeln!(f, "DynamicUser={}", yes_no(dynamic_user))?;
+        }
+        write_each(f, "CapabilityBoundingSet", &self.capability_bounding_set)?;
+        write_list(f, "AmbientCapabilities", &self.ambient_capabilities)?;
+        write_list(f, "ReadOnlyPaths", &self.read_only_paths)?;
+        write_list(f, "ReadWritePaths", &self.read_write_paths)?;
+        write_list(f, "InaccessiblePaths", &self.inaccessible_paths)?;
+        write_each(
+            f,
+            "RestrictAddressFamilies",
+            &self.restrict_address_families,
+        )?;
+        write_each(f, "SystemCallFilter", &self.system_call_filter)?;
+        if let Some(protect_kernel_tunables) = self.protect_kernel_tunables {
+            writeln!(
+                f,
+                "ProtectKernelTunables={}",
+                yes_no(protect_kernel_tunables)
+            )?;
+        }
+        if let Some(protect_kernel_modules) = self.protect_kernel_modules {
+            writeln!(f, "ProtectKernelModules={}", yes_no(protect_kernel_modules))?;
+        }
+        if let Some(protect_kernel_logs) = self.protect_kernel_logs {
+            writeln!(f, "ProtectKernelLogs={}", yes_no(protect_kernel_logs))?;
+        }
+        if let Some(memory_deny_write_execute) = self.memory_deny_write_execute {
+            writeln!(
+                f,
+                "MemoryDenyWriteExecute={}",
+                yes_no(memory_deny_write_execute)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct SecurityConfigurationBuilder<'a> {
+    pub protect_system: Option<ProtectSystem>,
+    pub protect_home: Option<ProtectHome>,
+    pub private_tmp: Option<bool>,
+    pub private_devices: Option<bool>,
+    pub private_network: Option<bool>,
+    pub no_new_privileges: Option<bool>,
+    pub dynamic_user: Option<bool>,
+    pub capability_bounding_set: Vec<&'a str>,
+    pub ambient_capabilities: Vec<&'a str>,
+    pub read_only_paths: Vec<&'a str>,
+    pub read_write_paths: Vec<&'a str>,
+    pub inaccessible_paths: Vec<&'a str>,
+    pub restrict_address_families: Vec<AddressFamily<'a>>,
+    pub system_call_filter: Vec<&'a str>,
+    pub protect_kernel_tunables: Option<bool>,
+    pub protect_kernel_modules: Option<bool>,
+    pub protect_kernel_logs: Option<bool>,
+    pub memory_deny_write_execute: Option<bool>,
+}
+
+impl<'a> SecurityConfigurationBuilder<'a> {
+    pub fn protect_system(mut self, protect_system: ProtectSystem) -> Self {
+        self.protect_system = Some(protect_system);
+        self
+    }
+
+    pub fn protect_home(mut self, protect_home: ProtectHome) -> Self {
+        self.protect_home = Some(protect_home);
+        self
+    }
+
+    pub fn private_tmp(mut self, private_tmp: bool) -> Self {
+        self.private_tmp = Some(private_tmp);
+        self
+    }
+
+    pub fn private_devices(mut self, private_devices: bool) -> Self {
+        self.private_devices = Some(private_devices);
+        self
+    }
+
+    pub fn private_network(mut self, private_network: bool) -> Self {
+        self.private_network = Some(private_network);
+        self
+    }
+
+    pub fn no_new_privileges(mut self, no_new_privileges: bool) -> Self {
+        self.no_new_privileges = Some(no_new_privileges);
+        self
+    }
+
+    pub fn dynamic_user(mut self, dynamic_user: bool) -> Self {
+        self.dynamic_user = Some(dynamic_user);
+        self
+    }
+
+    pub fn capability_bounding_set(mut self, capability: &'a str) -> Self {
+        self.capability_bounding_set.push(capability);
+        self
+    }
+
+    pub fn ambient_capabilities(mut self, capability: &'a str) -> Self {
+        self.ambient_capabilities.push(capability);
+        self
+    }
+
+    pub fn read_only_paths(mut self, path: &'a str) -> Self {
+        self.read_only_paths.push(path);
+        self
+    }
+
+    pub fn read_write_paths(mut self, path: &'a str) -> Self {
+        self.read_write_paths.push(path);
+        self
+    }
+
+    pub fn inaccessible_paths(mut self, path: &'a str) -> Self {
+        self.inaccessible_paths.push(path);
+        self
+    }
+
+    pub fn restrict_address_families(mut self, family: AddressFamily<'a>) -> Self {
+        self.restrict_address_families.push(family);
+        self
+    }
+
+    pub fn system_call_filter(mut self, system_call: &'a str) -> Self {
+        self.system_call_filter.push(system_call);
+        self
+    }
+
+    pub fn protect_kernel_tunables(mut self, protect_kernel_tunables: bool) -> Self {
+        self.protect_kernel_tunables = Some(protect_kernel_tunables);
+        self
+    }
+
+    pub fn protect_kernel_modules(mut self, protect_kernel_modules: bool) -> Self {
+        self.protect_kernel_modules = Some(protect_kernel_modules);
+        self
+    }
+
+    pub fn protect_kernel_logs(mut self, protect_kernel_logs: bool) -> Self {
+        self.protect_kernel_logs = Some(protect_kernel_logs);
+        self
+    }
+
+    pub fn memory_deny_write_execute(mut self, memory_deny_write_execute: bool) -> Self {
+        self.memory_deny_write_execute = Some(memory_deny_write_execute);
+        self
+    }
+
+    pub fn build(self) -> SecurityConfiguration<'a> {
+        SecurityConfiguration {
+            protect_system: self.protect_system,
+            protect_home: self.protect_home,
+            private_tmp: self.private_tmp,
+            private_devices: self.private_devices,
+            private_network: self.private_network,
+            no_new_privileges: self.no_new_privileges,
+            dynamic_user: self.dynamic_user,
+            capability_bounding_set: self.capability_bounding_set,
+            ambient_capabilities: self.ambient_capabilities,
+            read_only_paths: self.read_only_paths,
+            read_write_paths: self.read_write_paths,
+            inaccessible_paths: self.inaccessible_paths,
+            restrict_address_families: self.restrict_address_families,
+            system_call_filter: self.system_call_filter,
+            protect_kernel_tunables: self.protect_kernel_tunables,
+            protect_kernel_modules: self.protect_kernel_modules,
+            protect_kernel_logs: self.protect_kernel_logs,
+            memory_deny_write_execute: self.memory_deny_write_execute,
+        }
+    }
+}
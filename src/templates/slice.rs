@@ -0,0 +1,51 @@
+use super::{write_resets, ResourceControlConfiguration, ResourceControlConfigurationBuilder};
+use std::fmt::Display;
+
+// https://www.freedesktop.org/software/systemd/man/systemd.slice.html
+#[derive(Default)]
+pub struct SliceConfiguration<'a> {
+    pub resource_control: ResourceControlConfiguration<'a>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> SliceConfiguration<'a> {
+    pub fn builder() -> SliceConfigurationBuilder<'a> {
+        SliceConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for SliceConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Slice]")?;
+        write_resets(f, &self.resets)?;
+        self.resource_control.write_settings(f, false)
+    }
+}
+
+#[derive(Default)]
+pub struct SliceConfigurationBuilder<'a> {
+    pub resource_control: ResourceControlConfigurationBuilder<'a>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> SliceConfigurationBuilder<'a> {
+    pub fn resource_control(
+        mut self,
+        resource_control: ResourceControlConfigurationBuilder<'a>,
+    ) -> Self {
+        self.resource_control = resource_control;
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    pub fn build(self) -> SliceConfiguration<'a> {
+        SliceConfiguration {
+            resource_control: self.resource_control.build(),
+            resets: self.resets,
+        }
+    }
+}
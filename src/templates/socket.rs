@@ -0,0 +1,300 @@
+use super::{
+    write_resets, yes_no, ResourceControlConfiguration, ResourceControlConfigurationBuilder,
+    ServiceUnitConfiguration, UnitFile, UnitFileBuilder,
+};
+use crate::{escape_specifiers, ErrorImpl, Result, UnitName, UnitType};
+use std::fmt::Display;
+use std::net::SocketAddr;
+
+pub enum SocketAddress<'a> {
+    // port on all addresses, e.g. "8080"
+    Port(u16),
+    // e.g. "127.0.0.1:8080", "[::1]:8080"
+    Inet(SocketAddr),
+    // file system socket, e.g. "/run/test.sock"
+    Unix(&'a str),
+    // abstract namespace socket, rendered with "@" prefix
+    Abstract(&'a str),
+}
+
+impl<'a> Display for SocketAddress<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SocketAddress::Port(port) => write!(f, "{}", port),
+            // the scope id of a link-local address, "[fe80::1%2]:80", is no specifier
+            SocketAddress::Inet(addr) => write!(f, "{}", escape_specifiers(&addr.to_string())),
+            SocketAddress::Unix(path) => write!(f, "{}", path),
+            SocketAddress::Abstract(name) => write!(f, "@{}", name),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.socket.html#ListenStream=
+pub enum Listen<'a> {
+    Stream(SocketAddress<'a>),
+    Datagram(SocketAddress<'a>),
+    SequentialPacket(SocketAddress<'a>),
+    Fifo(&'a str),
+    // netlink family, e.g. "kobject-uevent", and optional multicast group
+    Netlink(&'a str, Option<u32>),
+}
+
+impl<'a> Display for Listen<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Listen::Stream(address) => write!(f, "ListenStream={}", address),
+            Listen::Datagram(address) => write!(f, "ListenDatagram={}", address),
+            Listen::SequentialPacket(address) => {
+                write!(f, "ListenSequentialPacket={}", address)
+            }
+            Listen::Fifo(path) => write!(f, "ListenFIFO={}", path),
+            Listen::Netlink(family, Some(group)) => {
+                write!(f, "ListenNetlink={} {}", family, group)
+            }
+            Listen::Netlink(family, None) => write!(f, "ListenNetlink={}", family),
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.socket.html#Options
+#[derive(Default)]
+pub struct SocketConfiguration<'a> {
+    pub listen: Vec<Listen<'a>>,
+    // spawn one service instance per connection, requires a template service
+    pub accept: Option<bool>,
+    pub socket_user: Option<&'a str>,
+    pub socket_group: Option<&'a str>,
+    pub socket_mode: Option<u32>,
+    pub backlog: Option<u32>,
+    pub file_descriptor_name: Option<&'a str>,
+    // service to activate, defaults to the service with the same name as the socket
+    pub service: Option<&'a str>,
+    pub resource_control: ResourceControlConfiguration<'a>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> SocketConfiguration<'a> {
+    pub fn builder() -> SocketConfigurationBuilder<'a> {
+        SocketConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for SocketConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Socket]")?;
+        write_resets(f, &self.resets)?;
+        for listen in self.listen.iter() {
+            writeln!(f, "{}", listen)?;
+        }
+        if let Some(accept) = self.accept {
+            writeln!(f, "Accept={}", yes_no(accept))?;
+        }
+        if let Some(socket_user) = self.socket_user {
+            writeln!(f, "SocketUser={}", socket_user)?;
+        }
+        if let Some(socket_group) = self.socket_group {
+            writeln!(f, "SocketGroup={}", socket_group)?;
+        }
+        if let Some(socket_mode) = self.socket_mode {
+            writeln!(f, "SocketMode={:04o}", socket_mode)?;
+        }
+        if let Some(backlog) = self.backlog {
+            writeln!(f, "Backlog={}", backlog)?;
+        }
+        if let Some(file_descriptor_name) = self.file_descriptor_name {
+            writeln!(f, "FileDescriptorName={}", file_descriptor_name)?;
+        }
+        if let Some(service) = self.service {
+            writeln!(f, "Service={}", service)?;
+        }
+        write!(f, "{}", self.resource_control)
+    }
+}
+
+#[derive(Default)]
+pub struct SocketConfigurationBuilder<'a> {
+    pub listen: Vec<Listen<'a>>,
+    pub accept: Option<bool>,
+    pub socket_user: Option<&'a str>,
+    pub socket_group: Option<&'a str>,
+    pub socket_mode: Option<u32>,
+    pub backlog: Option<u32>,
+    pub file_descriptor_name: Option<&'a str>,
+    pub service: Option<&'a str>,
+    pub resource_control: ResourceControlConfigurationBuilder<'a>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> SocketConfigurationBuilder<'a> {
+    pub fn listen(mut self, listen: Listen<'a>) -> Self {
+        self.listen.push(listen);
+        self
+    }
+
+    pub fn accept(mut self, accept: bool) -> Self {
+        self.accept = Some(accept);
+        self
+    }
+
+    pub fn socket_user(mut self, socket_user: &'a str) -> Self {
+        self.socket_user = Some(socket_user);
+        self
+    }
+
+    pub fn socket_group(mut self, socket_group: &'a str) -> Self {
+        self.socket_group = Some(socket_group);
+        self
+    }
+
+    pub fn socket_mode(mut self, socket_mode: u32) -> Self {
+        self.socket_mode = Some(socket_mode);
+        self
+    }
+
+    pub fn backlog(mut self, backlog: u32) -> Self {
+        self.backlog = Some(backlog);
+        self
+    }
+
+    pub fn file_descriptor_name(mut self, file_descriptor_name: &'a str) -> Self {
+        self.file_descriptor_name = Some(file_descriptor_name);
+        self
+    }
+
+    pub fn service(mut self, service: &'a str) -> Self {
+        self.service = Some(service);
+        self
+    }
+
+    pub fn resource_control(
+        mut self,
+        resource_control: ResourceControlConfigurationBuilder<'a>,
+    ) -> Self {
+        self.resource_control = resource_control;
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    pub fn build(self) -> SocketConfiguration<'a> {
+        SocketConfiguration {
+            listen: self.listen,
+            accept: self.accept,
+            socket_user: self.socket_user,
+            socket_group: self.socket_group,
+            socket_mode: self.socket_mode,
+            backlog: self.backlog,
+            file_descriptor_name: self.file_descriptor_name,
+            service: self.service,
+            resource_control: self.resource_control.build(),
+            resets: self.resets,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.socket.html
+// see SocketActivation for pairing it with the activated service
+pub type SocketUnitConfiguration<'a> = UnitFile<'a, SocketConfiguration<'a>>;
+pub type SocketUnitConfigurationBuilder<'a> = UnitFileBuilder<'a, SocketConfiguration<'a>>;
+
+impl<'a> SocketUnitConfiguration<'a> {
+    pub fn socket(&self) -> &SocketConfiguration<'a> {
+        &self.section
+    }
+}
+
+impl<'a> SocketUnitConfigurationBuilder<'a> {
+    pub fn socket(self, socket: SocketConfigurationBuilder<'a>) -> Self {
+        self.section(socket)
+    }
+}
+
+// a socket unit and the service it activates, named so that systemd pairs them up
+// with Accept=yes every connection starts an instance of the "<name>@.service" template,
+// otherwise Service= or the service named like the socket is started
+pub struct SocketActivation<'a> {
+    socket_name: UnitName,
+    service_name: UnitName,
+    socket: SocketUnitConfiguration<'a>,
+    service: ServiceUnitConfiguration<'a>,
+}
+
+impl<'a> SocketActivation<'a> {
+    pub fn new(
+        socket_name: &str,
+        socket: SocketUnitConfiguration<'a>,
+        service: ServiceUnitConfiguration<'a>,
+    ) -> Result<SocketActivation<'a>> {
+        let socket_name: UnitName = socket_name.parse()?;
+        if socket_name.unit_type() != UnitType::Socket {
+            return Err(
+                ErrorImpl::InvalidUnitName(socket_name.to_string(), "not a socket unit").into(),
+            );
+        }
+        if socket_name.is_template() || socket_name.is_instance() {
+            return Err(ErrorImpl::InvalidUnitName(
+                socket_name.to_string(),
+                "socket templates are not supported",
+            )
+            .into());
+        }
+        let accept = socket.section.accept.unwrap_or(false);
+        let service_name: UnitName = match (accept, socket.section.service) {
+            (true, Some(service)) => {
+                return Err(ErrorImpl::InvalidValue(
+                    service.to_owned(),
+                    "Service= together with Accept=yes",
+                )
+                .into())
+            }
+            (true, None) => format!("{}@.service", socket_name.prefix()).parse()?,
+            (false, Some(service)) => service.parse()?,
+            (false, None) => format!("{}.service", socket_name.prefix()).parse()?,
+        };
+        if service_name.unit_type() != UnitType::Service {
+            return Err(
+                ErrorImpl::InvalidUnitName(service_name.to_string(), "not a service unit").into(),
+            );
+        }
+        if !accept && service_name.is_template() {
+            return Err(ErrorImpl::InvalidUnitName(
+                service_name.to_string(),
+                "a template service requires Accept=yes",
+            )
+            .into());
+        }
+        Ok(SocketActivation {
+            socket_name,
+            service_name,
+            socket,
+            service,
+        })
+    }
+
+    pub fn socket_name(&self) -> &UnitName {
+        &self.socket_name
+    }
+
+    pub fn service_name(&self) -> &UnitName {
+        &self.service_name
+    }
+
+    pub fn socket(&self) -> &SocketUnitConfiguration<'a> {
+        &self.socket
+    }
+
+    pub fn service(&self) -> &ServiceUnitConfiguration<'a> {
+        &self.service
+    }
+
+    // unit names and file contents, e.g. for create_unit_configuration_file
+    pub fn render(&self) -> Vec<(UnitName, String)> {
+        vec![
+            (self.socket_name.clone(), self.socket.to_string()),
+            (self.service_name.clone(), self.service.to_string()),
+        ]
+    }
+}
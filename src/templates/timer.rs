@@ -0,0 +1,180 @@
+use super::{write_resets, yes_no, UnitFile, UnitFileBuilder};
+use std::fmt::Display;
+
+// https://www.freedesktop.org/software/systemd/man/systemd.timer.html#Options
+#[derive(Default)]
+pub struct TimerConfiguration<'a> {
+    // calendar event expressions, e.g. "*-*-* 04:00:00"
+    pub on_calendar: Vec<&'a str>,
+    // time spans, a unit-less value in seconds or e.g. "5min 20s"
+    pub on_active_sec: Option<&'a str>,
+    pub on_boot_sec: Option<&'a str>,
+    pub on_startup_sec: Option<&'a str>,
+    pub on_unit_active_sec: Option<&'a str>,
+    pub on_unit_inactive_sec: Option<&'a str>,
+    pub accuracy_sec: Option<&'a str>,
+    pub randomized_delay_sec: Option<&'a str>,
+    pub persistent: Option<bool>,
+    pub wake_system: Option<bool>,
+    // unit to activate, defaults to the service with the same name as the timer
+    pub unit: Option<&'a str>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> TimerConfiguration<'a> {
+    pub fn builder() -> TimerConfigurationBuilder<'a> {
+        TimerConfigurationBuilder::default()
+    }
+}
+
+impl<'a> Display for TimerConfiguration<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "[Timer]")?;
+        write_resets(f, &self.resets)?;
+        for on_calendar in self.on_calendar.iter() {
+            writeln!(f, "OnCalendar={}", on_calendar)?;
+        }
+        if let Some(on_active_sec) = self.on_active_sec {
+            writeln!(f, "OnActiveSec={}", on_active_sec)?;
+        }
+        if let Some(on_boot_sec) = self.on_boot_sec {
+            writeln!(f, "OnBootSec={}", on_boot_sec)?;
+        }
+        if let Some(on_startup_sec) = self.on_startup_sec {
+            writeln!(f, "OnStartupSec={}", on_startup_sec)?;
+        }
+        if let Some(on_unit_active_sec) = self.on_unit_active_sec {
+            writeln!(f, "OnUnitActiveSec={}", on_unit_active_sec)?;
+        }
+        if let Some(on_unit_inactive_sec) = self.on_unit_inactive_sec {
+            writeln!(f, "OnUnitInactiveSec={}", on_unit_inactive_sec)?;
+        }
+        if let Some(accuracy_sec) = self.accuracy_sec {
+            writeln!(f, "AccuracySec={}", accuracy_sec)?;
+        }
+        if let Some(randomized_delay_sec) = self.randomized_delay_sec {
+            writeln!(f, "RandomizedDelaySec={}", randomized_delay_sec)?;
+        }
+        if let Some(persistent) = self.persistent {
+            writeln!(f, "Persistent={}", yes_no(persistent))?;
+        }
+        if let Some(wake_system) = self.wake_system {
+            writeln!(f, "WakeSystem={}", yes_no(wake_system))?;
+        }
+        if let Some(unit) = self.unit {
+            writeln!(f, "Unit={}", unit)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+pub struct TimerConfigurationBuilder<'a> {
+    pub on_calendar: Vec<&'a str>,
+    pub on_active_sec: Option<&'a str>,
+    pub on_boot_sec: Option<&'a str>,
+    pub on_startup_sec: Option<&'a str>,
+    pub on_unit_active_sec: Option<&'a str>,
+    pub on_unit_inactive_sec: Option<&'a str>,
+    pub accuracy_sec: Option<&'a str>,
+    pub randomized_delay_sec: Option<&'a str>,
+    pub persistent: Option<bool>,
+    pub wake_system: Option<bool>,
+    pub unit: Option<&'a str>,
+    pub resets: Vec<&'a str>,
+}
+
+impl<'a> TimerConfigurationBuilder<'a> {
+    pub fn on_calendar(mut self, on_calendar: &'a str) -> Self {
+        self.on_calendar.push(on_calendar);
+        self
+    }
+
+    pub fn on_active_sec(mut self, on_active_sec: &'a str) -> Self {
+        self.on_active_sec = Some(on_active_sec);
+        self
+    }
+
+    pub fn on_boot_sec(mut self, on_boot_sec: &'a str) -> Self {
+        self.on_boot_sec = Some(on_boot_sec);
+        self
+    }
+
+    pub fn on_startup_sec(mut self, on_startup_sec: &'a str) -> Self {
+        self.on_startup_sec = Some(on_startup_sec);
+        self
+    }
+
+    pub fn on_unit_active_sec(mut self, on_unit_active_sec: &'a str) -> Self {
+        self.on_unit_active_sec = Some(on_unit_active_sec);
+        self
+    }
+
+    pub fn on_unit_inactive_sec(mut self, on_unit_inactive_sec: &'a str) -> Self {
+        self.on_unit_inactive_sec = Some(on_unit_inactive_sec);
+        self
+    }
+
+    pub fn accuracy_sec(mut self, accuracy_sec: &'a str) -> Self {
+        self.accuracy_sec = Some(accuracy_sec);
+        self
+    }
+
+    pub fn randomized_delay_sec(mut self, randomized_delay_sec: &'a str) -> Self {
+        self.randomized_delay_sec = Some(randomized_delay_sec);
+        self
+    }
+
+    pub fn persistent(mut self, persistent: bool) -> Self {
+        self.persistent = Some(persistent);
+        self
+    }
+
+    pub fn wake_system(mut self, wake_system: bool) -> Self {
+        self.wake_system = Some(wake_system);
+        self
+    }
+
+    pub fn unit(mut self, unit: &'a str) -> Self {
+        self.unit = Some(unit);
+        self
+    }
+
+    pub fn reset(mut self, key: &'a str) -> Self {
+        self.resets.push(key);
+        self
+    }
+
+    pub fn build(self) -> TimerConfiguration<'a> {
+        TimerConfiguration {
+            on_calendar: self.on_calendar,
+            on_active_sec: self.on_active_sec,
+            on_boot_sec: self.on_boot_sec,
+            on_startup_sec: self.on_startup_sec,
+            on_unit_active_sec: self.on_unit_active_sec,
+            on_unit_inactive_sec: self.on_unit_inactive_sec,
+            accuracy_sec: self.accuracy_sec,
+            randomized_delay_sec: self.randomized_delay_sec,
+            persistent: self.persistent,
+            wake_system: self.wake_system,
+            unit: self.unit,
+            resets: self.resets,
+        }
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.timer.html
+pub type TimerUnitConfiguration<'a> = UnitFile<'a, TimerConfiguration<'a>>;
+pub type TimerUnitConfigurationBuilder<'a> = UnitFileBuilder<'a, TimerConfiguration<'a>>;
+
+impl<'a> TimerUnitConfiguration<'a> {
+    pub fn timer(&self) -> &TimerConfiguration<'a> {
+        &self.section
+    }
+}
+
+impl<'a> TimerUnitConfigurationBuilder<'a> {
+    pub fn timer(self, timer: TimerConfigurationBuilder<'a>) -> Self {
+        self.section(timer)
+    }
+}
@@ -0,0 +1,177 @@
+use super::{
+    AutomountConfiguration, AutomountConfigurationBuilder, InstallConfiguration,
+    InstallConfigurationBuilder, MountConfiguration, MountConfigurationBuilder, PathConfiguration,
+    PathConfigurationBuilder, ServiceConfiguration, ServiceConfigurationBuilder,
+    SliceConfiguration, SliceConfigurationBuilder, SocketConfiguration, SocketConfigurationBuilder,
+    TimerConfiguration, TimerConfigurationBuilder, UnitConfiguration, UnitConfigurationBuilder,
+};
+use crate::{Result, UnitName, UnitType};
+use std::fmt::Display;
+
+// type specific section of a unit file, e.g. [Service] or [Timer]
+pub trait UnitSection: Display {
+    type Builder: Default;
+    const UNIT_TYPE: UnitType;
+    // WantedBy= used when [Install] sets none
+    const DEFAULT_WANTED_BY: Option<&'static str>;
+
+    fn from_builder(builder: Self::Builder) -> Self;
+}
+
+impl<'a> UnitSection for ServiceConfiguration<'a> {
+    type Builder = ServiceConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Service;
+    const DEFAULT_WANTED_BY: Option<&'static str> = Some("multi-user.target");
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+impl<'a> UnitSection for TimerConfiguration<'a> {
+    type Builder = TimerConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Timer;
+    const DEFAULT_WANTED_BY: Option<&'static str> = Some("timers.target");
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+impl<'a> UnitSection for SocketConfiguration<'a> {
+    type Builder = SocketConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Socket;
+    const DEFAULT_WANTED_BY: Option<&'static str> = Some("sockets.target");
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+impl<'a> UnitSection for PathConfiguration<'a> {
+    type Builder = PathConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Path;
+    const DEFAULT_WANTED_BY: Option<&'static str> = Some("paths.target");
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+// mounts are usually pulled in by local-fs.target or remote-fs.target,
+// which one depends on the file system, so no default is assumed
+impl<'a> UnitSection for MountConfiguration<'a> {
+    type Builder = MountConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Mount;
+    const DEFAULT_WANTED_BY: Option<&'static str> = None;
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+impl<'a> UnitSection for AutomountConfiguration<'a> {
+    type Builder = AutomountConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Automount;
+    const DEFAULT_WANTED_BY: Option<&'static str> = None;
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+// slices are started implicitly by the units placed in them
+impl<'a> UnitSection for SliceConfiguration<'a> {
+    type Builder = SliceConfigurationBuilder<'a>;
+    const UNIT_TYPE: UnitType = UnitType::Slice;
+    const DEFAULT_WANTED_BY: Option<&'static str> = None;
+
+    fn from_builder(builder: Self::Builder) -> Self {
+        builder.build()
+    }
+}
+
+// [Unit] + one type specific section + [Install]
+// the section type fixes the unit type, so e.g. a timer file can never carry a [Service] section
+pub struct UnitFile<'a, S: UnitSection> {
+    pub unit: UnitConfiguration<'a>,
+    pub section: S,
+    pub install: InstallConfiguration<'a>,
+}
+
+impl<'a, S: UnitSection> UnitFile<'a, S> {
+    pub fn builder() -> UnitFileBuilder<'a, S> {
+        UnitFileBuilder::default()
+    }
+
+    pub fn unit_type(&self) -> UnitType {
+        S::UNIT_TYPE
+    }
+}
+
+impl<'a> UnitFile<'a, MountConfiguration<'a>> {
+    pub fn unit_name(&self) -> Result<UnitName> {
+        self.section.unit_name()
+    }
+}
+
+impl<'a> UnitFile<'a, AutomountConfiguration<'a>> {
+    pub fn unit_name(&self) -> Result<UnitName> {
+        self.section.unit_name()
+    }
+}
+
+impl<'a, S: UnitSection> Display for UnitFile<'a, S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}\n{}", self.unit, self.section)?;
+        if !self.install.is_empty() {
+            write!(f, "\n{}", self.install)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct UnitFileBuilder<'a, S: UnitSection> {
+    pub unit: UnitConfigurationBuilder<'a>,
+    pub section: S::Builder,
+    pub install: InstallConfigurationBuilder<'a>,
+}
+
+// derive(Default) would require S: Default
+impl<'a, S: UnitSection> Default for UnitFileBuilder<'a, S> {
+    fn default() -> Self {
+        UnitFileBuilder {
+            unit: UnitConfigurationBuilder::default(),
+            section: S::Builder::default(),
+            install: InstallConfigurationBuilder::default(),
+        }
+    }
+}
+
+impl<'a, S: UnitSection> UnitFileBuilder<'a, S> {
+    pub fn unit(mut self, unit: UnitConfigurationBuilder<'a>) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn section(mut self, section: S::Builder) -> Self {
+        self.section = section;
+        self
+    }
+
+    pub fn install(mut self, install: InstallConfigurationBuilder<'a>) -> Self {
+        self.install = install;
+        self
+    }
+
+    pub fn build(self) -> UnitFile<'a, S> {
+        let unit = self.unit.build();
+        let section = S::from_builder(self.section);
+        let install = self.install.build_for_unit_type(S::DEFAULT_WANTED_BY);
+        UnitFile {
+            unit,
+            section,
+            install,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{Result, TimerCalendarTuple, TimerMonotonicTuple, TimerProps};
+
+#[zbus::dbus_proxy(
+    interface = "org.freedesktop.systemd1.Timer",
+    default_service = "org.freedesktop.systemd1"
+)]
+trait SystemdTimer {
+    #[dbus_proxy(property)]
+    fn unit(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn timers_monotonic(&self) -> zbus::Result<Vec<TimerMonotonicTuple>>;
+    #[dbus_proxy(property)]
+    fn timers_calendar(&self) -> zbus::Result<Vec<TimerCalendarTuple>>;
+    #[dbus_proxy(property, name = "NextElapseUSecRealtime")]
+    fn next_elapse_usec_realtime(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "NextElapseUSecMonotonic")]
+    fn next_elapse_usec_monotonic(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property, name = "LastTriggerUSec")]
+    fn last_trigger_usec(&self) -> zbus::Result<u64>;
+    #[dbus_proxy(property)]
+    fn result(&self) -> zbus::Result<String>;
+    #[dbus_proxy(property)]
+    fn persistent(&self) -> zbus::Result<bool>;
+}
+
+impl SystemdTimerProxyBlocking<'_> {
+    pub fn get_properties(&self) -> zbus::Result<TimerProps> {
+        let timer_props = TimerProps::builder()
+            .unit(self.unit()?)
+            .timers_monotonic(self.timers_monotonic()?)
+            .timers_calendar(self.timers_calendar()?)
+            .next_elapse_realtime(self.next_elapse_usec_realtime()?)
+            .next_elapse_monotonic(self.next_elapse_usec_monotonic()?)
+            .last_trigger(self.last_trigger_usec()?)
+            .result(self.result()?)
+            .persistent(self.persistent()?)
+            .build();
+        Ok(timer_props)
+    }
+}
+
+impl SystemdTimerProxy<'_> {
+    pub async fn get_properties(&self) -> zbus::Result<TimerProps> {
+        let timer_props = TimerProps::builder()
+            .unit(self.unit().await?)
+            .timers_monotonic(self.timers_monotonic().await?)
+            .timers_calendar(self.timers_calendar().await?)
+            .next_elapse_realtime(self.next_elapse_usec_realtime().await?)
+            .next_elapse_monotonic(self.next_elapse_usec_monotonic().await?)
+            .last_trigger(self.last_trigger_usec().await?)
+            .result(self.result().await?)
+            .persistent(self.persistent().await?)
+            .build();
+        Ok(timer_props)
+    }
+}
+
+pub async fn build_nonblock_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdTimerProxy<'static>> {
+    let connection = zbus::Connection::system().await?;
+    let proxy = SystemdTimerProxy::builder(&connection)
+        .path(object)?
+        .build()
+        .await?;
+    Ok(proxy)
+}
+
+pub fn build_blocking_proxy(
+    object: zvariant::OwnedObjectPath,
+) -> Result<SystemdTimerProxyBlocking<'static>> {
+    let connection = zbus::blocking::Connection::system()?;
+    let proxy = SystemdTimerProxyBlocking::builder(&connection)
+        .path(object)?
+        .build()?;
+    Ok(proxy)
+}
@@ -1,3 +1,5 @@
 mod proxy;
+mod typed;
 
 pub use proxy::*;
+pub use typed::*;
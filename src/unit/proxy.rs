@@ -5,6 +5,23 @@ use crate::{Result, UnitProps};
     default_service = "org.freedesktop.systemd1"
 )]
 trait SystemdUnit {
+    fn start(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn stop(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn reload(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn restart(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn try_restart(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn reload_or_restart(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn reload_or_try_restart(&self, mode: &str) -> zbus::Result<zvariant::OwnedObjectPath>;
+    fn kill(&self, who: &str, signal: i32) -> zbus::Result<()>;
+    fn reset_failed(&self) -> zbus::Result<()>;
+    fn set_properties(
+        &self,
+        runtime: bool,
+        properties: &[(&str, zvariant::Value<'_>)],
+    ) -> zbus::Result<()>;
+    #[dbus_proxy(name = "Ref")]
+    fn ref_(&self) -> zbus::Result<()>;
+    fn unref(&self) -> zbus::Result<()>;
     #[dbus_proxy(property)]
     fn id(&self) -> zbus::Result<String>;
     #[dbus_proxy(property)]
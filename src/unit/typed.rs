@@ -0,0 +1,75 @@
+use crate::{
+    automount, device, mount, path, scope, service, slice, socket, swap, target, timer, Result,
+    Unit, UnitType,
+};
+
+// proxy of the type-specific interface of a unit, picked by the type suffix of the unit name
+pub enum TypedUnitProxy<'a> {
+    Service(service::SystemdServiceProxy<'a>),
+    Socket(socket::SystemdSocketProxy<'a>),
+    Target(target::SystemdTargetProxy<'a>),
+    Device(device::SystemdDeviceProxy<'a>),
+    Mount(mount::SystemdMountProxy<'a>),
+    Automount(automount::SystemdAutomountProxy<'a>),
+    Swap(swap::SystemdSwapProxy<'a>),
+    Timer(timer::SystemdTimerProxy<'a>),
+    Path(path::SystemdPathProxy<'a>),
+    Slice(slice::SystemdSliceProxy<'a>),
+    Scope(scope::SystemdScopeProxy<'a>),
+}
+
+pub enum TypedUnitProxyBlocking<'a> {
+    Service(service::SystemdServiceProxyBlocking<'a>),
+    Socket(socket::SystemdSocketProxyBlocking<'a>),
+    Target(target::SystemdTargetProxyBlocking<'a>),
+    Device(device::SystemdDeviceProxyBlocking<'a>),
+    Mount(mount::SystemdMountProxyBlocking<'a>),
+    Automount(automount::SystemdAutomountProxyBlocking<'a>),
+    Swap(swap::SystemdSwapProxyBlocking<'a>),
+    Timer(timer::SystemdTimerProxyBlocking<'a>),
+    Path(path::SystemdPathProxyBlocking<'a>),
+    Slice(slice::SystemdSliceProxyBlocking<'a>),
+    Scope(scope::SystemdScopeProxyBlocking<'a>),
+}
+
+pub async fn build_typed_nonblock_proxy(unit: &Unit) -> Result<TypedUnitProxy<'static>> {
+    let object = unit.object_path.clone();
+    let proxy = match unit.unit_name()?.unit_type() {
+        UnitType::Service => TypedUnitProxy::Service(service::build_nonblock_proxy(object).await?),
+        UnitType::Socket => TypedUnitProxy::Socket(socket::build_nonblock_proxy(object).await?),
+        UnitType::Target => TypedUnitProxy::Target(target::build_nonblock_proxy(object).await?),
+        UnitType::Device => TypedUnitProxy::Device(device::build_nonblock_proxy(object).await?),
+        UnitType::Mount => TypedUnitProxy::Mount(mount::build_nonblock_proxy(object).await?),
+        UnitType::Automount => {
+            TypedUnitProxy::Automount(automount::build_nonblock_proxy(object).await?)
+        }
+        UnitType::Swap => TypedUnitProxy::Swap(swap::build_nonblock_proxy(object).await?),
+        UnitType::Timer => TypedUnitProxy::Timer(timer::build_nonblock_proxy(object).await?),
+        UnitType::Path => TypedUnitProxy::Path(path::build_nonblock_proxy(object).await?),
+        UnitType::Slice => TypedUnitProxy::Slice(slice::build_nonblock_proxy(object).await?),
+        UnitType::Scope => TypedUnitProxy::Scope(scope::build_nonblock_proxy(object).await?),
+    };
+    Ok(proxy)
+}
+
+pub fn build_typed_blocking_proxy(unit: &Unit) -> Result<TypedUnitProxyBlocking<'static>> {
+    let object = unit.object_path.clone();
+    let proxy = match unit.unit_name()?.unit_type() {
+        UnitType::Service => {
+            TypedUnitProxyBlocking::Service(service::build_blocking_proxy(object)?)
+        }
+        UnitType::Socket => TypedUnitProxyBlocking::Socket(socket::build_blocking_proxy(object)?),
+        UnitType::Target => TypedUnitProxyBlocking::Target(target::build_blocking_proxy(object)?),
+        UnitType::Device => TypedUnitProxyBlocking::Device(device::build_blocking_proxy(object)?),
+        UnitType::Mount => TypedUnitProxyBlocking::Mount(mount::build_blocking_proxy(object)?),
+        UnitType::Automount => {
+            TypedUnitProxyBlocking::Automount(automount::build_blocking_proxy(object)?)
+        }
+        UnitType::Swap => TypedUnitProxyBlocking::Swap(swap::build_blocking_proxy(object)?),
+        UnitType::Timer => TypedUnitProxyBlocking::Timer(timer::build_blocking_proxy(object)?),
+        UnitType::Path => TypedUnitProxyBlocking::Path(path::build_blocking_proxy(object)?),
+        UnitType::Slice => TypedUnitProxyBlocking::Slice(slice::build_blocking_proxy(object)?),
+        UnitType::Scope => TypedUnitProxyBlocking::Scope(scope::build_blocking_proxy(object)?),
+    };
+    Ok(proxy)
+}
@@ -1,6 +1,10 @@
-use crate::{Result, SYSTEMD_UNIT_CONFIGURATION_DIRECTORY};
+use crate::{
+    validate_unit_file, Diagnostic, DiagnosticLevel, ErrorImpl, Result, UnitName, UnitType,
+    SYSTEMD_UNIT_CONFIGURATION_DIRECTORY,
+};
 
 use std::io::Write;
+use std::path::Path;
 
 pub fn create_unit_configuration_file(unit_name: &str, buffer: &[u8]) -> Result<()> {
     let mut path = std::path::PathBuf::from(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY);
@@ -12,9 +16,261 @@ pub fn create_unit_configuration_file(unit_name: &str, buffer: &[u8]) -> Result<
     Ok(())
 }
 
+// refuse to write a unit file systemd would fail to load, warnings are returned to the caller
+pub fn create_validated_unit_configuration_file(
+    unit_name: &str,
+    buffer: &[u8],
+) -> Result<Vec<Diagnostic>> {
+    let name: UnitName = unit_name.parse()?;
+    let content = std::str::from_utf8(buffer)
+        .map_err(|err| ErrorImpl::InvalidUnitFile(unit_name.to_owned(), err.to_string()))?;
+    let diagnostics = validate_unit_file(content, &name)?;
+    if let Some(error) = diagnostics
+        .iter()
+        .find(|diagnostic| diagnostic.level == DiagnosticLevel::Error)
+    {
+        return Err(ErrorImpl::InvalidUnitFile(unit_name.to_owned(), error.to_string()).into());
+    }
+    create_unit_configuration_file(unit_name, buffer)?;
+    Ok(diagnostics)
+}
+
 pub fn delete_unit_configuration_file(unit_name: &str) -> Result<()> {
     let mut path = std::path::PathBuf::from(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY);
     path.push(unit_name);
     std::fs::remove_file(path.as_path())?;
     Ok(())
 }
+
+// wall clock time the system booted at, from the btime line of /proc/stat
+// time spent in suspend is not accounted for, as with CLOCK_MONOTONIC
+pub fn boot_time() -> Result<std::time::SystemTime> {
+    let stat = std::fs::read_to_string("/proc/stat")?;
+    let btime = stat
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))
+        .and_then(|btime| btime.trim().parse::<u64>().ok())
+        .ok_or_else(|| ErrorImpl::InvalidValue(String::from("/proc/stat"), "btime"))?;
+    Ok(std::time::UNIX_EPOCH + std::time::Duration::from_secs(btime))
+}
+
+// <directory>/<unit>.d/<name>.conf
+fn drop_in_file_path(
+    directory: &Path,
+    unit_name: &UnitName,
+    name: &str,
+) -> Result<std::path::PathBuf> {
+    if name.is_empty() || name.contains('/') {
+        return Err(ErrorImpl::InvalidPath(format!("{}.d/{}.conf", unit_name, name)).into());
+    }
+    let mut path = directory.join(format!("{}.d", unit_name));
+    path.push(format!("{}.conf", name));
+    Ok(path)
+}
+
+pub fn create_drop_in_file(unit_name: &UnitName, name: &str, buffer: &[u8]) -> Result<()> {
+    create_drop_in_file_at(
+        Path::new(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY),
+        unit_name,
+        name,
+        buffer,
+    )
+}
+
+// the *_at variants take the unit directory instead of /etc/systemd/system
+pub fn create_drop_in_file_at(
+    directory: &Path,
+    unit_name: &UnitName,
+    name: &str,
+    buffer: &[u8],
+) -> Result<()> {
+    let path = drop_in_file_path(directory, unit_name, name)?;
+    if let Some(directory) = path.parent() {
+        std::fs::create_dir_all(directory)?;
+    }
+    let file = std::fs::File::create(path.as_path())?;
+    let mut writer = std::io::BufWriter::new(file);
+    writer.write_all(buffer)?;
+    writer.flush()?;
+    Ok(())
+}
+
+// names of the drop-ins of a unit in lexicographic order, which is the order systemd applies them in
+pub fn list_drop_in_files(unit_name: &UnitName) -> Result<Vec<String>> {
+    list_drop_in_files_at(Path::new(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY), unit_name)
+}
+
+pub fn list_drop_in_files_at(directory: &Path, unit_name: &UnitName) -> Result<Vec<String>> {
+    let path = directory.join(format!("{}.d", unit_name));
+    let entries = match std::fs::read_dir(path.as_path()) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(err.into()),
+    };
+    let mut names = vec![];
+    for entry in entries {
+        let file_name = entry?.file_name().into_string().map_err(|file_name| {
+            ErrorImpl::InvalidPath(file_name.to_string_lossy().into_owned())
+        })?;
+        if let Some(name) = file_name.strip_suffix(".conf") {
+            names.push(name.to_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+// the drop-in directory is removed as well once it is empty
+pub fn delete_drop_in_file(unit_name: &UnitName, name: &str) -> Result<()> {
+    delete_drop_in_file_at(
+        Path::new(SYSTEMD_UNIT_CONFIGURATION_DIRECTORY),
+        unit_name,
+        name,
+    )
+}
+
+pub fn delete_drop_in_file_at(directory: &Path, unit_name: &UnitName, name: &str) -> Result<()> {
+    let path = drop_in_file_path(directory, unit_name, name)?;
+    std::fs::remove_file(path.as_path())?;
+    if let Some(directory) = path.parent() {
+        if std::fs::read_dir(directory)?.next().is_none() {
+            std::fs::remove_dir(directory)?;
+        }
+    }
+    Ok(())
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd-escape.html
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#String%20Escaping%20for%20Inclusion%20in%20Unit%20Names
+fn push_escaped_byte(escaped: &mut String, byte: u8) {
+    escaped.push_str(&format!("\\x{:02x}", byte));
+}
+
+fn escape_bytes(bytes: &[u8], escaped: &mut String) {
+    for (i, &byte) in bytes.iter().enumerate() {
+        match byte {
+            b'/' => escaped.push('-'),
+            // do not create units with a leading '.', like for "/.dotdir" mount points
+            b'.' if i == 0 => push_escaped_byte(escaped, byte),
+            b':' | b'_' | b'.' => escaped.push(byte as char),
+            _ if byte.is_ascii_alphanumeric() => escaped.push(byte as char),
+            _ => push_escaped_byte(escaped, byte),
+        }
+    }
+}
+
+fn invalid_escape(escaped: &str) -> crate::Error {
+    ErrorImpl::InvalidEscape(escaped.to_owned()).into()
+}
+
+// "%" => "%%", so systemd takes the value literally instead of expanding specifiers
+pub fn escape_specifiers(value: &str) -> String {
+    value.replace('%', "%%")
+}
+
+// systemd-escape <string>
+pub fn escape_unit_name(origin: &str) -> String {
+    let mut escaped = String::with_capacity(origin.len());
+    escape_bytes(origin.as_bytes(), &mut escaped);
+    escaped
+}
+
+// systemd-escape --unescape <string>
+pub fn unescape_unit_name(escaped: &str) -> Result<String> {
+    let bytes = escaped.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'-' => unescaped.push(b'/'),
+            b'\\' => {
+                if bytes.get(i + 1) != Some(&b'x') {
+                    return Err(invalid_escape(escaped));
+                }
+                let hex = escaped
+                    .get(i + 2..i + 4)
+                    .ok_or_else(|| invalid_escape(escaped))?;
+                if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                    return Err(invalid_escape(escaped));
+                }
+                let byte = u8::from_str_radix(hex, 16).map_err(|_| invalid_escape(escaped))?;
+                unescaped.push(byte);
+                i += 3;
+            }
+            byte => unescaped.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8(unescaped).map_err(|_| invalid_escape(escaped))
+}
+
+// collapse duplicate slashes and "." components, None if the path contains ".."
+fn normalize_path(path: &str) -> Option<String> {
+    let mut components = vec![];
+    for component in path.split('/') {
+        match component {
+            "" | "." => continue,
+            ".." => return None,
+            component => components.push(component),
+        }
+    }
+    Some(components.join("/"))
+}
+
+// systemd-escape --path <path>
+pub fn escape_unit_path(path: &str) -> Result<String> {
+    let normalized = normalize_path(path).ok_or_else(|| ErrorImpl::InvalidPath(path.to_owned()))?;
+    if normalized.is_empty() {
+        return Ok(String::from("-"));
+    }
+    Ok(escape_unit_name(&normalized))
+}
+
+// systemd-escape --unescape --path <string>
+pub fn unescape_unit_path(escaped: &str) -> Result<String> {
+    if escaped.is_empty() {
+        return Err(invalid_escape(escaped));
+    }
+    if escaped == "-" {
+        return Ok(String::from("/"));
+    }
+    let unescaped = unescape_unit_name(escaped)?;
+    let path = format!("/{}", unescaped);
+    match normalize_path(&path) {
+        Some(normalized) if format!("/{}", normalized) == path => Ok(path),
+        _ => Err(ErrorImpl::InvalidPath(path).into()),
+    }
+}
+
+// systemd-escape --mangle --suffix=<unit type> <string>
+// valid unit names are kept, absolute paths become .device or .mount units
+// and anything else is escaped and suffixed with the given unit type if it has none
+pub fn mangle_unit_name(origin: &str, unit_type: UnitType) -> Result<UnitName> {
+    if let Ok(unit_name) = origin.parse::<UnitName>() {
+        return Ok(unit_name);
+    }
+    // like is_device_path() of systemd
+    if origin.starts_with("/dev/") || origin.starts_with("/sys/") {
+        return format!("{}.{}", escape_unit_path(origin)?, UnitType::Device).parse();
+    }
+    if origin.starts_with('/') {
+        return format!("{}.{}", escape_unit_path(origin)?, UnitType::Mount).parse();
+    }
+    let mut mangled = String::with_capacity(origin.len());
+    for byte in origin.bytes() {
+        match byte {
+            b'/' => mangled.push('-'),
+            b':' | b'-' | b'_' | b'.' | b'\\' | b'@' => mangled.push(byte as char),
+            _ if byte.is_ascii_alphanumeric() => mangled.push(byte as char),
+            _ => push_escaped_byte(&mut mangled, byte),
+        }
+    }
+    let has_suffix = matches!(
+        mangled.rsplit_once('.'),
+        Some((_, suffix)) if suffix.parse::<UnitType>().is_ok()
+    );
+    if !has_suffix {
+        mangled = format!("{}.{}", mangled, unit_type);
+    }
+    mangled.parse()
+}
@@ -0,0 +1,1084 @@
+use crate::{
+    escape_unit_path, parser::parse_boolean, Directive, DocumentSection, Result, UnitDocument,
+    UnitName, UnitType,
+};
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+
+// in-process checks similar to `systemd-analyze verify`
+// https://www.freedesktop.org/software/systemd/man/systemd-analyze.html
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLevel {
+    // systemd refuses to load the unit or the setting
+    Error,
+    // systemd ignores the setting
+    Warning,
+}
+
+impl Display for DiagnosticLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let level = match self {
+            DiagnosticLevel::Error => "error",
+            DiagnosticLevel::Warning => "warning",
+        };
+        write!(f, "{}", level)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub line: Option<usize>,
+    // file the offending section was read from, if the document was loaded from disk
+    pub origin: Option<PathBuf>,
+    pub section: String,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.level)?;
+        if let Some(origin) = &self.origin {
+            write!(f, "{}:", origin.display())?;
+        }
+        if let Some(line) = self.line {
+            write!(f, "{}:", line)?;
+        }
+        if self.origin.is_some() || self.line.is_some() {
+            write!(f, " ")?;
+        }
+        write!(f, "[{}]", self.section)?;
+        if let Some(key) = &self.key {
+            write!(f, " {}=", key)?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
+
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#%5BUnit%5D%20Section%20Options
+const UNIT_KEYS: &[&str] = &[
+    "Description",
+    "Documentation",
+    "Wants",
+    "Requires",
+    "Requisite",
+    "BindsTo",
+    "PartOf",
+    "Upholds",
+    "Conflicts",
+    "Before",
+    "After",
+    "OnFailure",
+    "OnSuccess",
+    "PropagatesReloadTo",
+    "ReloadPropagatedFrom",
+    "PropagatesStopTo",
+    "StopPropagatedFrom",
+    "JoinsNamespaceOf",
+    "RequiresMountsFor",
+    "WantsMountsFor",
+    "OnFailureJobMode",
+    "IgnoreOnIsolate",
+    "StopWhenUnneeded",
+    "RefuseManualStart",
+    "RefuseManualStop",
+    "AllowIsolate",
+    "DefaultDependencies",
+    "SurviveFinalKillSignal",
+    "CollectMode",
+    "FailureAction",
+    "SuccessAction",
+    "FailureActionExitStatus",
+    "SuccessActionExitStatus",
+    "JobTimeoutSec",
+    "JobRunningTimeoutSec",
+    "JobTimeoutAction",
+    "JobTimeoutRebootArgument",
+    "StartLimitIntervalSec",
+    "StartLimitBurst",
+    "StartLimitAction",
+    "RebootArgument",
+    "SourcePath",
+];
+
+// ConditionX= and AssertX= share the same checks
+// https://www.freedesktop.org/software/systemd/man/systemd.unit.html#Conditions%20and%20Asserts
+const CONDITIONS: &[&str] = &[
+    "Architecture",
+    "Firmware",
+    "Virtualization",
+    "Host",
+    "KernelCommandLine",
+    "KernelVersion",
+    "Credential",
+    "Environment",
+    "Security",
+    "Capability",
+    "ACPower",
+    "NeedsUpdate",
+    "FirstBoot",
+    "PathExists",
+    "PathExistsGlob",
+    "PathIsDirectory",
+    "PathIsSymbolicLink",
+    "PathIsMountPoint",
+    "PathIsReadWrite",
+    "PathIsEncrypted",
+    "DirectoryNotEmpty",
+    "FileNotEmpty",
+    "FileIsExecutable",
+    "User",
+    "Group",
+    "ControlGroupController",
+    "Memory",
+    "CPUs",
+    "CPUFeature",
+    "OSRelease",
+    "MemoryPressure",
+    "CPUPressure",
+    "IOPressure",
+];
+
+const INSTALL_KEYS: &[&str] = &[
+    "Alias",
+    "WantedBy",
+    "RequiredBy",
+    "UpheldBy",
+    "Also",
+    "DefaultInstance",
+];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.service.html#Options
+const SERVICE_KEYS: &[&str] = &[
+    "Type",
+    "ExitType",
+    "RemainAfterExit",
+    "GuessMainPID",
+    "PIDFile",
+    "BusName",
+    "ExecStart",
+    "ExecStartPre",
+    "ExecStartPost",
+    "ExecCondition",
+    "ExecReload",
+    "ExecStop",
+    "ExecStopPost",
+    "RestartSec",
+    "RestartSteps",
+    "RestartMaxDelaySec",
+    "TimeoutStartSec",
+    "TimeoutStopSec",
+    "TimeoutAbortSec",
+    "TimeoutSec",
+    "TimeoutStartFailureMode",
+    "TimeoutStopFailureMode",
+    "RuntimeMaxSec",
+    "RuntimeRandomizedExtraSec",
+    "WatchdogSec",
+    "Restart",
+    "RestartMode",
+    "SuccessExitStatus",
+    "RestartPreventExitStatus",
+    "RestartForceExitStatus",
+    "RootDirectoryStartOnly",
+    "NonBlocking",
+    "NotifyAccess",
+    "Sockets",
+    "FileDescriptorStoreMax",
+    "FileDescriptorStorePreserve",
+    "USBFunctionDescriptors",
+    "USBFunctionStrings",
+    "OOMPolicy",
+    "OpenFile",
+    "ReloadSignal",
+    "PermissionsStartOnly",
+];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.socket.html#Options
+const SOCKET_KEYS: &[&str] = &[
+    "ListenStream",
+    "ListenDatagram",
+    "ListenSequentialPacket",
+    "ListenFIFO",
+    "ListenSpecial",
+    "ListenNetlink",
+    "ListenMessageQueue",
+    "ListenUSBFunction",
+    "SocketProtocol",
+    "BindIPv6Only",
+    "Backlog",
+    "BindToDevice",
+    "SocketUser",
+    "SocketGroup",
+    "SocketMode",
+    "DirectoryMode",
+    "Accept",
+    "Writable",
+    "FlushPending",
+    "MaxConnections",
+    "MaxConnectionsPerSource",
+    "KeepAlive",
+    "KeepAliveTimeSec",
+    "KeepAliveIntervalSec",
+    "KeepAliveProbes",
+    "NoDelay",
+    "Priority",
+    "DeferAcceptSec",
+    "ReceiveBuffer",
+    "SendBuffer",
+    "IPTOS",
+    "IPTTL",
+    "Mark",
+    "ReusePort",
+    "SmackLabel",
+    "SmackLabelIPIn",
+    "SmackLabelIPOut",
+    "SELinuxContextFromNet",
+    "PipeSize",
+    "MessageQueueMaxMessages",
+    "MessageQueueMessageSize",
+    "FreeBind",
+    "Transparent",
+    "Broadcast",
+    "PassCredentials",
+    "PassSecurity",
+    "PassPacketInfo",
+    "Timestamping",
+    "TCPCongestion",
+    "ExecStartPre",
+    "ExecStartPost",
+    "ExecStopPre",
+    "ExecStopPost",
+    "TimeoutSec",
+    "Service",
+    "RemoveOnStop",
+    "Symlinks",
+    "FileDescriptorName",
+    "TriggerLimitIntervalSec",
+    "TriggerLimitBurst",
+    "PollLimitIntervalSec",
+    "PollLimitBurst",
+];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.timer.html#Options
+const TIMER_KEYS: &[&str] = &[
+    "OnActiveSec",
+    "OnBootSec",
+    "OnStartupSec",
+    "OnUnitActiveSec",
+    "OnUnitInactiveSec",
+    "OnCalendar",
+    "AccuracySec",
+    "RandomizedDelaySec",
+    "FixedRandomDelay",
+    "OnClockChange",
+    "OnTimezoneChange",
+    "Unit",
+    "Persistent",
+    "WakeSystem",
+    "RemainAfterElapse",
+];
+
+const MOUNT_KEYS: &[&str] = &[
+    "What",
+    "Where",
+    "Type",
+    "Options",
+    "SloppyOptions",
+    "LazyUnmount",
+    "ReadWriteOnly",
+    "ForceUnmount",
+    "DirectoryMode",
+    "TimeoutSec",
+];
+
+const AUTOMOUNT_KEYS: &[&str] = &["Where", "ExtraOptions", "DirectoryMode", "TimeoutIdleSec"];
+
+const SWAP_KEYS: &[&str] = &["What", "Priority", "Options", "TimeoutSec"];
+
+const PATH_KEYS: &[&str] = &[
+    "PathExists",
+    "PathExistsGlob",
+    "PathChanged",
+    "PathModified",
+    "DirectoryNotEmpty",
+    "Unit",
+    "MakeDirectory",
+    "DirectoryMode",
+    "TriggerLimitIntervalSec",
+    "TriggerLimitBurst",
+];
+
+const SCOPE_KEYS: &[&str] = &["RuntimeMaxSec", "RuntimeRandomizedExtraSec", "OOMPolicy"];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.exec.html
+const EXEC_KEYS: &[&str] = &[
+    "WorkingDirectory",
+    "RootDirectory",
+    "RootImage",
+    "RootImageOptions",
+    "RootEphemeral",
+    "RootHash",
+    "RootHashSignature",
+    "RootVerity",
+    "RootImagePolicy",
+    "MountImagePolicy",
+    "ExtensionImagePolicy",
+    "MountAPIVFS",
+    "ProtectProc",
+    "ProcSubset",
+    "BindPaths",
+    "BindReadOnlyPaths",
+    "MountImages",
+    "ExtensionImages",
+    "ExtensionDirectories",
+    "User",
+    "Group",
+    "DynamicUser",
+    "SupplementaryGroups",
+    "SetLoginEnvironment",
+    "PAMName",
+    "CapabilityBoundingSet",
+    "AmbientCapabilities",
+    "NoNewPrivileges",
+    "SecureBits",
+    "SELinuxContext",
+    "AppArmorProfile",
+    "SmackProcessLabel",
+    "LimitCPU",
+    "LimitFSIZE",
+    "LimitDATA",
+    "LimitSTACK",
+    "LimitCORE",
+    "LimitRSS",
+    "LimitNOFILE",
+    "LimitAS",
+    "LimitNPROC",
+    "LimitMEMLOCK",
+    "LimitLOCKS",
+    "LimitSIGPENDING",
+    "LimitMSGQUEUE",
+    "LimitNICE",
+    "LimitRTPRIO",
+    "LimitRTTIME",
+    "UMask",
+    "CoredumpFilter",
+    "KeyringMode",
+    "OOMScoreAdjust",
+    "TimerSlackNSec",
+    "Personality",
+    "IgnoreSIGPIPE",
+    "Nice",
+    "CPUSchedulingPolicy",
+    "CPUSchedulingPriority",
+    "CPUSchedulingResetOnFork",
+    "CPUAffinity",
+    "NUMAPolicy",
+    "NUMAMask",
+    "IOSchedulingClass",
+    "IOSchedulingPriority",
+    "ProtectSystem",
+    "ProtectHome",
+    "RuntimeDirectory",
+    "StateDirectory",
+    "CacheDirectory",
+    "LogsDirectory",
+    "ConfigurationDirectory",
+    "RuntimeDirectoryMode",
+    "StateDirectoryMode",
+    "CacheDirectoryMode",
+    "LogsDirectoryMode",
+    "ConfigurationDirectoryMode",
+    "RuntimeDirectoryPreserve",
+    "TimeoutCleanSec",
+    "ReadWritePaths",
+    "ReadOnlyPaths",
+    "InaccessiblePaths",
+    "ExecPaths",
+    "NoExecPaths",
+    "TemporaryFileSystem",
+    "PrivateTmp",
+    "PrivateDevices",
+    "PrivateNetwork",
+    "NetworkNamespacePath",
+    "PrivateIPC",
+    "IPCNamespacePath",
+    "MemoryKSM",
+    "PrivateUsers",
+    "ProtectHostname",
+    "ProtectClock",
+    "ProtectKernelTunables",
+    "ProtectKernelModules",
+    "ProtectKernelLogs",
+    "ProtectControlGroups",
+    "RestrictAddressFamilies",
+    "RestrictFileSystems",
+    "RestrictNamespaces",
+    "LockPersonality",
+    "MemoryDenyWriteExecute",
+    "RestrictRealtime",
+    "RestrictSUIDSGID",
+    "RemoveIPC",
+    "PrivateMounts",
+    "MountFlags",
+    "SystemCallFilter",
+    "SystemCallErrorNumber",
+    "SystemCallArchitectures",
+    "SystemCallLog",
+    "Environment",
+    "EnvironmentFile",
+    "PassEnvironment",
+    "UnsetEnvironment",
+    "StandardInput",
+    "StandardOutput",
+    "StandardError",
+    "StandardInputText",
+    "StandardInputData",
+    "LogLevelMax",
+    "LogExtraFields",
+    "LogRateLimitIntervalSec",
+    "LogRateLimitBurst",
+    "LogFilterPatterns",
+    "LogNamespace",
+    "SyslogIdentifier",
+    "SyslogFacility",
+    "SyslogLevel",
+    "SyslogLevelPrefix",
+    "TTYPath",
+    "TTYReset",
+    "TTYVHangup",
+    "TTYRows",
+    "TTYColumns",
+    "TTYVTDisallocate",
+    "LoadCredential",
+    "LoadCredentialEncrypted",
+    "ImportCredential",
+    "SetCredential",
+    "SetCredentialEncrypted",
+    "UtmpIdentifier",
+    "UtmpMode",
+];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.kill.html
+const KILL_KEYS: &[&str] = &[
+    "KillMode",
+    "KillSignal",
+    "RestartKillSignal",
+    "SendSIGHUP",
+    "SendSIGKILL",
+    "FinalKillSignal",
+    "WatchdogSignal",
+];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.resource-control.html
+const RESOURCE_CONTROL_KEYS: &[&str] = &[
+    "CPUAccounting",
+    "CPUWeight",
+    "StartupCPUWeight",
+    "CPUQuota",
+    "CPUQuotaPeriodSec",
+    "AllowedCPUs",
+    "StartupAllowedCPUs",
+    "AllowedMemoryNodes",
+    "StartupAllowedMemoryNodes",
+    "MemoryAccounting",
+    "MemoryMin",
+    "MemoryLow",
+    "StartupMemoryLow",
+    "DefaultStartupMemoryLow",
+    "MemoryHigh",
+    "StartupMemoryHigh",
+    "MemoryMax",
+    "StartupMemoryMax",
+    "MemorySwapMax",
+    "StartupMemorySwapMax",
+    "MemoryZSwapMax",
+    "StartupMemoryZSwapMax",
+    "MemoryZSwapWriteback",
+    "TasksAccounting",
+    "TasksMax",
+    "IOAccounting",
+    "IOWeight",
+    "StartupIOWeight",
+    "IODeviceWeight",
+    "IOReadBandwidthMax",
+    "IOWriteBandwidthMax",
+    "IOReadIOPSMax",
+    "IOWriteIOPSMax",
+    "IODeviceLatencyTargetSec",
+    "IPAccounting",
+    "IPAddressAllow",
+    "IPAddressDeny",
+    "SocketBindAllow",
+    "SocketBindDeny",
+    "RestrictNetworkInterfaces",
+    "NFTSet",
+    "IPIngressFilterPath",
+    "IPEgressFilterPath",
+    "BPFProgram",
+    "DeviceAllow",
+    "DevicePolicy",
+    "Slice",
+    "Delegate",
+    "DelegateSubgroup",
+    "DisableControllers",
+    "ManagedOOMSwap",
+    "ManagedOOMMemoryPressure",
+    "ManagedOOMMemoryPressureLimit",
+    "ManagedOOMPreference",
+    "MemoryPressureWatch",
+    "MemoryPressureThresholdSec",
+    "CoredumpReceive",
+    "CPUShares",
+    "StartupCPUShares",
+    "MemoryLimit",
+    "BlockIOAccounting",
+    "BlockIOWeight",
+    "StartupBlockIOWeight",
+    "BlockIODeviceWeight",
+    "BlockIOReadBandwidth",
+    "BlockIOWriteBandwidth",
+];
+
+// settings taking a single byte size, e.g. "512M", "10%" or "infinity"
+const SIZE_KEYS: &[&str] = &[
+    "MemoryMin",
+    "MemoryLow",
+    "StartupMemoryLow",
+    "DefaultStartupMemoryLow",
+    "MemoryHigh",
+    "StartupMemoryHigh",
+    "MemoryMax",
+    "StartupMemoryMax",
+    "MemorySwapMax",
+    "StartupMemorySwapMax",
+    "MemoryZSwapMax",
+    "StartupMemoryZSwapMax",
+    "ReceiveBuffer",
+    "SendBuffer",
+    "PipeSize",
+];
+
+// settings taking "<path> <size>"
+const DEVICE_SIZE_KEYS: &[&str] = &["IOReadBandwidthMax", "IOWriteBandwidthMax"];
+
+// keys ending in "Sec" that are not a plain time span
+const NON_TIME_SPAN_KEYS: &[&str] = &["IODeviceLatencyTargetSec"];
+
+// settings that require an absolute path, optionally prefixed with "-" to ignore a missing one
+const ABSOLUTE_PATH_KEYS: &[&str] = &[
+    "WorkingDirectory",
+    "RootDirectory",
+    "RootImage",
+    "EnvironmentFile",
+    "TTYPath",
+    "Where",
+    "ListenFIFO",
+    "ListenSpecial",
+    "PathExists",
+    "PathExistsGlob",
+    "PathChanged",
+    "PathModified",
+    "DirectoryNotEmpty",
+];
+
+// space separated lists of absolute paths, entries optionally prefixed with "-" or "+"
+const ABSOLUTE_PATH_LIST_KEYS: &[&str] = &[
+    "RequiresMountsFor",
+    "WantsMountsFor",
+    "ReadWritePaths",
+    "ReadOnlyPaths",
+    "InaccessiblePaths",
+    "ExecPaths",
+    "NoExecPaths",
+];
+
+// https://www.freedesktop.org/software/systemd/man/systemd.time.html#Parsing%20Time%20Spans
+const TIME_SPAN_UNITS: &[&str] = &[
+    "", "usec", "us", "µs", "msec", "ms", "seconds", "second", "sec", "s", "minutes", "minute",
+    "min", "m", "hours", "hour", "hr", "h", "days", "day", "d", "weeks", "week", "w", "months",
+    "month", "M", "years", "year", "y",
+];
+
+// accepted in addition by the *NSec= settings
+const NSEC_UNITS: &[&str] = &["nsec", "ns"];
+
+// type specific section and the option groups it shares with other unit types
+fn section_keys(unit_type: UnitType) -> Option<(&'static str, Vec<&'static [&'static str]>)> {
+    let section = match unit_type {
+        UnitType::Service => (
+            "Service",
+            vec![SERVICE_KEYS, EXEC_KEYS, KILL_KEYS, RESOURCE_CONTROL_KEYS],
+        ),
+        UnitType::Socket => (
+            "Socket",
+            vec![SOCKET_KEYS, EXEC_KEYS, KILL_KEYS, RESOURCE_CONTROL_KEYS],
+        ),
+        UnitType::Mount => (
+            "Mount",
+            vec![MOUNT_KEYS, EXEC_KEYS, KILL_KEYS, RESOURCE_CONTROL_KEYS],
+        ),
+        UnitType::Swap => (
+            "Swap",
+            vec![SWAP_KEYS, EXEC_KEYS, KILL_KEYS, RESOURCE_CONTROL_KEYS],
+        ),
+        UnitType::Timer => ("Timer", vec![TIMER_KEYS]),
+        UnitType::Automount => ("Automount", vec![AUTOMOUNT_KEYS]),
+        UnitType::Path => ("Path", vec![PATH_KEYS]),
+        UnitType::Slice => ("Slice", vec![RESOURCE_CONTROL_KEYS]),
+        UnitType::Scope => ("Scope", vec![SCOPE_KEYS, KILL_KEYS, RESOURCE_CONTROL_KEYS]),
+        UnitType::Target | UnitType::Device => return None,
+    };
+    Some(section)
+}
+
+fn is_known_key(section: &str, key: &str, type_section: Option<&(&str, Vec<&[&str]>)>) -> bool {
+    // vendor extensions
+    if key.starts_with("X-") {
+        return true;
+    }
+    match section {
+        "Unit" => {
+            UNIT_KEYS.contains(&key)
+                || key
+                    .strip_prefix("Condition")
+                    .or_else(|| key.strip_prefix("Assert"))
+                    .is_some_and(|condition| CONDITIONS.contains(&condition))
+        }
+        "Install" => INSTALL_KEYS.contains(&key),
+        // shared with the other resource controlled units, but a slice has no parent setting
+        "Slice" if key == "Slice" => false,
+        _ => type_section.is_some_and(|(_, groups)| groups.iter().any(|keys| keys.contains(&key))),
+    }
+}
+
+// e.g. "5min 20s", "1h30m", "100ms", "2.5" or "infinity"
+fn is_time_span(value: &str, nsec: bool) -> bool {
+    if value == "infinity" {
+        return true;
+    }
+    let mut rest = value.trim_start();
+    while !rest.is_empty() {
+        let number_len = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .unwrap_or(rest.len());
+        if rest[..number_len].parse::<f64>().is_err() {
+            return false;
+        }
+        rest = rest[number_len..].trim_start();
+        let unit_len = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = &rest[..unit_len];
+        let known = TIME_SPAN_UNITS.contains(&unit) || (nsec && NSEC_UNITS.contains(&unit));
+        if !known {
+            return false;
+        }
+        rest = rest[unit_len..].trim_start();
+    }
+    true
+}
+
+// e.g. "1024", "512K", "1.5G", "20%" or "infinity", suffixes are to the base of 1024
+fn is_size(value: &str) -> bool {
+    if value == "infinity" {
+        return true;
+    }
+    let (number, max) = match value.strip_suffix('%') {
+        Some(percent) => (percent, 100.0),
+        None => (
+            value
+                .strip_suffix(['K', 'M', 'G', 'T', 'P', 'E'])
+                .unwrap_or(value),
+            f64::MAX,
+        ),
+    };
+    !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit() || c == '.')
+        && number.parse::<f64>().is_ok_and(|number| number <= max)
+}
+
+// specifiers such as %h or %t expand to absolute paths
+fn is_absolute_path(path: &str) -> bool {
+    path.starts_with('/') || path.starts_with('%')
+}
+
+struct Validator<'a> {
+    document: &'a UnitDocument,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    fn report(
+        &mut self,
+        level: DiagnosticLevel,
+        section: &DocumentSection,
+        directive: Option<&Directive>,
+        message: String,
+    ) {
+        self.diagnostics.push(Diagnostic {
+            level,
+            line: directive
+                .and_then(Directive::line)
+                .or_else(|| section.line()),
+            origin: section.origin().map(ToOwned::to_owned),
+            section: section.name().to_owned(),
+            key: directive.map(|directive| directive.key().to_owned()),
+            message,
+        });
+    }
+
+    fn error(&mut self, section: &DocumentSection, directive: &Directive, message: String) {
+        self.report(DiagnosticLevel::Error, section, Some(directive), message)
+    }
+
+    // a required setting is missing, reported against its section, or the [Unit] section if the
+    // whole section is missing, and finally against the document
+    fn missing(&mut self, name: &str, message: &str) {
+        match self
+            .first_section(name)
+            .or_else(|| self.first_section("Unit"))
+        {
+            Some(section) => self.report(DiagnosticLevel::Error, section, None, message.to_owned()),
+            None => self.diagnostics.push(Diagnostic {
+                level: DiagnosticLevel::Error,
+                line: None,
+                origin: None,
+                section: name.to_owned(),
+                key: None,
+                message: message.to_owned(),
+            }),
+        }
+    }
+
+    // last assignment of key across all sections with the given name, None if unset or reset
+    fn effective(&self, section: &str, key: &str) -> Option<(&'a DocumentSection, &'a Directive)> {
+        let document = self.document;
+        document
+            .sections()
+            .iter()
+            .filter(|s| s.name() == section)
+            .flat_map(|s| s.directives().map(move |directive| (s, directive)))
+            .filter(|(_, directive)| directive.key() == key)
+            .last()
+            .filter(|(_, directive)| !directive.is_reset())
+    }
+
+    // assignments of key after the last reset
+    fn effective_all(&self, section: &str, key: &str) -> Vec<(&'a DocumentSection, &'a Directive)> {
+        let mut assignments = vec![];
+        for s in self
+            .document
+            .sections()
+            .iter()
+            .filter(|s| s.name() == section)
+        {
+            for directive in s.directives().filter(|directive| directive.key() == key) {
+                match directive.is_reset() {
+                    true => assignments.clear(),
+                    false => assignments.push((s, directive)),
+                }
+            }
+        }
+        assignments
+    }
+
+    fn first_section(&self, name: &str) -> Option<&'a DocumentSection> {
+        let document = self.document;
+        document.section(name)
+    }
+
+    fn check_directives(&mut self, unit_type: UnitType) {
+        let type_section = section_keys(unit_type);
+        let document = self.document;
+        for section in document.sections() {
+            let name = section.name();
+            let known_section = name == "Unit"
+                || name == "Install"
+                || name.starts_with("X-")
+                || type_section
+                    .as_ref()
+                    .is_some_and(|(type_name, _)| *type_name == name);
+            if !known_section {
+                self.report(
+                    DiagnosticLevel::Warning,
+                    section,
+                    None,
+                    format!("unknown section for a {} unit, ignored", unit_type),
+                );
+                continue;
+            }
+            if name.starts_with("X-") {
+                continue;
+            }
+            for directive in section.directives() {
+                let key = directive.key();
+                if !is_known_key(name, key, type_section.as_ref()) {
+                    self.report(
+                        DiagnosticLevel::Warning,
+                        section,
+                        Some(directive),
+                        "unknown key, ignored".to_owned(),
+                    );
+                    continue;
+                }
+                if !directive.is_reset() {
+                    self.check_value(section, directive);
+                }
+            }
+        }
+    }
+
+    fn check_value(&mut self, section: &DocumentSection, directive: &Directive) {
+        let key = directive.key();
+        let value = directive.value();
+        // vendor extensions have no known format
+        if key.starts_with("X-") {
+            return;
+        }
+        if key.ends_with("Sec")
+            && !NON_TIME_SPAN_KEYS.contains(&key)
+            && !is_time_span(value, key.ends_with("NSec"))
+        {
+            self.error(
+                section,
+                directive,
+                format!("{:?} is not a valid time span", value),
+            );
+        }
+        if SIZE_KEYS.contains(&key) && !is_size(value) {
+            self.error(
+                section,
+                directive,
+                format!("{:?} is not a valid size", value),
+            );
+        }
+        if DEVICE_SIZE_KEYS.contains(&key) {
+            match value.split_whitespace().collect::<Vec<_>>().as_slice() {
+                [device, size] if is_absolute_path(device) && is_size(size) => {}
+                _ => self.error(
+                    section,
+                    directive,
+                    format!("{:?} is not a device path followed by a size", value),
+                ),
+            }
+        }
+        let is_path_condition = section.name() == "Unit"
+            && key
+                .strip_prefix("Condition")
+                .or_else(|| key.strip_prefix("Assert"))
+                .is_some_and(|condition| {
+                    condition.starts_with("Path")
+                        || condition.starts_with("File")
+                        || condition == "DirectoryNotEmpty"
+                });
+        if ABSOLUTE_PATH_KEYS.contains(&key) || is_path_condition {
+            let path = value.trim_start_matches(['-', '|', '!']);
+            // the home directory of User= is allowed as well
+            let home = key == "WorkingDirectory" && path == "~";
+            if !is_absolute_path(path) && !home {
+                self.error(
+                    section,
+                    directive,
+                    format!("{:?} is not an absolute path", value),
+                );
+            }
+        }
+        if ABSOLUTE_PATH_LIST_KEYS.contains(&key) {
+            for path in value.split_whitespace() {
+                if !is_absolute_path(path.trim_start_matches(['-', '+'])) {
+                    self.error(
+                        section,
+                        directive,
+                        format!("{:?} is not an absolute path", path),
+                    );
+                }
+            }
+        }
+    }
+
+    // runs without a [Service] section as well, systemd refuses such a service
+    fn check_service(&mut self) {
+        let exec_start = self.effective_all("Service", "ExecStart");
+        let has_bus_name = self.effective("Service", "BusName").is_some();
+        // without Type= systemd picks one, see service_add_extras()
+        let ty = match self.effective("Service", "Type") {
+            Some((_, directive)) => directive.value(),
+            None if has_bus_name => "dbus",
+            None if !exec_start.is_empty() => "simple",
+            None => "oneshot",
+        };
+        let has_stop = self.effective("Service", "ExecStop").is_some();
+        let has_action = self.effective("Unit", "SuccessAction").is_some();
+        if exec_start.is_empty() && !has_stop && !has_action {
+            self.missing(
+                "Service",
+                "service has no ExecStart=, ExecStop= or SuccessAction=",
+            );
+        } else if ty != "oneshot" {
+            match exec_start.as_slice() {
+                [] => self.missing("Service", &format!("Type={} requires ExecStart=", ty)),
+                [_] => {}
+                [_, (second_section, second), ..] => self.error(
+                    second_section,
+                    second,
+                    format!("Type={} allows only one ExecStart=", ty),
+                ),
+            }
+        }
+        if ty == "oneshot" {
+            if let Some((restart_section, restart)) = self.effective("Service", "Restart") {
+                if matches!(restart.value(), "always" | "on-success") {
+                    self.error(
+                        restart_section,
+                        restart,
+                        "Restart=always and Restart=on-success are not allowed for Type=oneshot"
+                            .to_owned(),
+                    );
+                }
+            }
+        }
+        if ty == "dbus" && !has_bus_name {
+            if let Some((type_section, directive)) = self.effective("Service", "Type") {
+                self.error(
+                    type_section,
+                    directive,
+                    "Type=dbus requires BusName=".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn check_socket(&mut self) {
+        let listens = [
+            "ListenStream",
+            "ListenDatagram",
+            "ListenSequentialPacket",
+            "ListenFIFO",
+            "ListenSpecial",
+            "ListenNetlink",
+            "ListenMessageQueue",
+            "ListenUSBFunction",
+        ];
+        if listens
+            .iter()
+            .all(|key| self.effective_all("Socket", key).is_empty())
+        {
+            self.missing("Socket", "socket has no Listen*= setting");
+        }
+        let accept = self
+            .effective("Socket", "Accept")
+            .and_then(|(_, directive)| parse_boolean(directive.value()))
+            .unwrap_or(false);
+        if let Some((service_section, service)) = self.effective("Socket", "Service") {
+            if accept {
+                self.error(
+                    service_section,
+                    service,
+                    "Service= cannot be combined with Accept=yes".to_owned(),
+                );
+            }
+        }
+    }
+
+    fn check_triggers(&mut self, name: &str, keys: &[&str], message: &str) {
+        if keys
+            .iter()
+            .all(|key| self.effective_all(name, key).is_empty())
+        {
+            self.missing(name, message);
+        }
+    }
+
+    // the unit name of mount and automount units is fixed by Where=
+    fn check_where(&mut self, unit_name: &UnitName, name: &str) {
+        let (section, directive) = match self.effective(name, "Where") {
+            Some(effective) => effective,
+            None => {
+                self.missing(name, "Where= is required");
+                return;
+            }
+        };
+        if unit_name.is_template() || unit_name.is_instance() {
+            return;
+        }
+        let expected = escape_unit_path(directive.value())
+            .map(|escaped| format!("{}.{}", escaped, unit_name.unit_type()));
+        match expected {
+            Ok(expected) if expected == unit_name.as_str() => {}
+            Ok(expected) => self.error(
+                section,
+                directive,
+                format!("unit must be named {:?} after its mount point", expected),
+            ),
+            Err(err) => self.error(section, directive, err.to_string()),
+        }
+    }
+}
+
+// check a unit file, e.g. a parsed one or the merged result of `LookupPaths`, for the given unit
+pub fn validate_unit_document(document: &UnitDocument, unit_name: &UnitName) -> Vec<Diagnostic> {
+    let mut validator = Validator {
+        document,
+        diagnostics: vec![],
+    };
+    let unit_type = unit_name.unit_type();
+    validator.check_directives(unit_type);
+    match unit_type {
+        UnitType::Service => validator.check_service(),
+        UnitType::Socket => validator.check_socket(),
+        UnitType::Timer => validator.check_triggers(
+            "Timer",
+            &[
+                "OnActiveSec",
+                "OnBootSec",
+                "OnStartupSec",
+                "OnUnitActiveSec",
+                "OnUnitInactiveSec",
+                "OnCalendar",
+                "OnClockChange",
+                "OnTimezoneChange",
+            ],
+            "timer has no On*= setting",
+        ),
+        UnitType::Path => validator.check_triggers(
+            "Path",
+            &[
+                "PathExists",
+                "PathExistsGlob",
+                "PathChanged",
+                "PathModified",
+                "DirectoryNotEmpty",
+            ],
+            "path unit has no Path*= or DirectoryNotEmpty= setting",
+        ),
+        UnitType::Mount => {
+            validator.check_triggers("Mount", &["What"], "What= is required");
+            validator.check_where(unit_name, "Mount");
+        }
+        UnitType::Automount => validator.check_where(unit_name, "Automount"),
+        _ => {}
+    }
+    // by file in the order the document merged them, then by line
+    let mut origins: Vec<Option<&Path>> = vec![];
+    for section in document.sections() {
+        if !origins.contains(&section.origin()) {
+            origins.push(section.origin());
+        }
+    }
+    validator.diagnostics.sort_by_key(|diagnostic| {
+        let origin = origins
+            .iter()
+            .position(|origin| *origin == diagnostic.origin.as_deref());
+        (origin, diagnostic.line.unwrap_or_default())
+    });
+    validator.diagnostics
+}
+
+// check rendered or hand written unit file content, syntax errors are returned as error
+pub fn validate_unit_file(content: &str, unit_name: &UnitName) -> Result<Vec<Diagnostic>> {
+    let document: UnitDocument = content.parse()?;
+    Ok(validate_unit_document(&document, unit_name))
+}
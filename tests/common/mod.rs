@@ -0,0 +1,54 @@
+// helpers shared by the integration tests, not every test file uses all of them
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use systemd_client::Unit;
+
+pub fn read_resource(name: &str) -> String {
+    let buffer = std::fs::read(format!("tests/resources/{}", name))
+        .unwrap_or_else(|_| panic!("open file '{}' failed", name));
+    String::from_utf8(buffer).unwrap_or_else(|_| panic!("invalid utf-8 in '{}'", name))
+}
+
+// loaded and running unit as listed by ListUnits
+pub fn unit(name: &str) -> Unit {
+    let object_path = zvariant::OwnedObjectPath::try_from("/org/freedesktop/systemd1/unit/test")
+        .expect("parse object path failed");
+    Unit::from((
+        name.to_owned(),
+        String::new(),
+        String::from("loaded"),
+        String::from("active"),
+        String::from("running"),
+        String::new(),
+        object_path.clone(),
+        0,
+        String::new(),
+        object_path,
+    ))
+}
+
+// directory under the system temp dir, removed again when dropped, also if an assert failed
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> TempDir {
+        let path =
+            std::env::temp_dir().join(format!("systemd_client_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).expect("create test directory failed");
+        TempDir { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use common::TempDir;
+use systemd_client::{
+    create_drop_in_file_at, delete_drop_in_file_at, list_drop_in_files_at, UnitName,
+};
+
+#[test]
+fn test_drop_in_files() {
+    let root = TempDir::new("drop_in");
+    let unit_name: UnitName = "test-app.service".parse().expect("invalid unit name");
+    assert!(list_drop_in_files_at(root.path(), &unit_name)
+        .expect("list drop-ins failed")
+        .is_empty());
+    create_drop_in_file_at(root.path(), &unit_name, "20-limits", b"[Service]\nNice=5\n")
+        .expect("create '20-limits' failed");
+    create_drop_in_file_at(root.path(), &unit_name, "10-env", b"[Service]\nUser=app\n")
+        .expect("create '10-env' failed");
+    let path = root.path().join("test-app.service.d/10-env.conf");
+    assert_eq!(
+        "[Service]\nUser=app\n",
+        std::fs::read_to_string(path).expect("read '10-env.conf' failed")
+    );
+    assert_eq!(
+        vec!["10-env", "20-limits"],
+        list_drop_in_files_at(root.path(), &unit_name).expect("list drop-ins failed")
+    );
+    for name in ["", "../10-env", "nested/10-env"] {
+        assert!(
+            create_drop_in_file_at(root.path(), &unit_name, name, b"").is_err(),
+            "{:?} accepted",
+            name
+        );
+    }
+    delete_drop_in_file_at(root.path(), &unit_name, "10-env").expect("delete '10-env' failed");
+    assert_eq!(
+        vec!["20-limits"],
+        list_drop_in_files_at(root.path(), &unit_name).expect("list drop-ins failed")
+    );
+    // the directory goes away with the last drop-in
+    delete_drop_in_file_at(root.path(), &unit_name, "20-limits")
+        .expect("delete '20-limits' failed");
+    assert!(!root.path().join("test-app.service.d").exists());
+    // names that could escape the directory are not valid unit names in the first place
+    assert!("../x".parse::<UnitName>().is_err());
+}
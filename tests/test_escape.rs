@@ -0,0 +1,126 @@
+use systemd_client::{
+    escape_unit_name, escape_unit_path, mangle_unit_name, unescape_unit_name, unescape_unit_path,
+    UnitType,
+};
+
+// deterministic pseudo random strings, biased towards characters with special meaning
+fn samples() -> Vec<String> {
+    let alphabet: Vec<char> = "abcXYZ019-_.:/\\@ %$\"'\tä€\u{1F600}".chars().collect();
+    let mut seed: u64 = 0x2545_f491_4f6c_dd1d;
+    let mut samples = vec![];
+    for len in 0..64 {
+        let mut sample = String::new();
+        for _ in 0..len {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            sample.push(alphabet[(seed % alphabet.len() as u64) as usize]);
+        }
+        samples.push(sample);
+    }
+    samples
+}
+
+#[test]
+fn test_escape_documented_examples() {
+    assert_eq!(
+        escape_unit_name("Hallöchen, Meister"),
+        "Hall\\xc3\\xb6chen\\x2c\\x20Meister"
+    );
+    assert_eq!(escape_unit_name("foo-bar/baz"), "foo\\x2dbar-baz");
+    assert_eq!(escape_unit_name(".dotfile"), "\\x2edotfile");
+    assert_eq!(
+        escape_unit_path("/tmp//waldi/foobar/").expect("escape path failed"),
+        "tmp-waldi-foobar"
+    );
+    assert_eq!(escape_unit_path("/").expect("escape root failed"), "-");
+    assert_eq!(
+        escape_unit_path("/var/lib/.hidden").expect("escape path failed"),
+        "var-lib-.hidden"
+    );
+    assert!(escape_unit_path("/var/../etc").is_err());
+    assert_eq!(
+        unescape_unit_name("Hall\\xc3\\xb6chen\\x2c\\x20Meister").expect("unescape failed"),
+        "Hallöchen, Meister"
+    );
+    assert_eq!(
+        unescape_unit_path("tmp-waldi-foobar").expect("unescape path failed"),
+        "/tmp/waldi/foobar"
+    );
+    assert_eq!(unescape_unit_path("-").expect("unescape root failed"), "/");
+}
+
+#[test]
+fn test_unescape_invalid() {
+    for escaped in ["\\", "\\x", "\\x2", "\\xzz", "\\x+f", "\\y20", "\\xff"] {
+        assert!(
+            unescape_unit_name(escaped).is_err(),
+            "{:?} should be invalid",
+            escaped
+        );
+    }
+    assert!(unescape_unit_path("").is_err());
+    assert!(unescape_unit_path("foo--bar").is_err());
+}
+
+#[test]
+fn test_escape_round_trip() {
+    for sample in samples() {
+        let escaped = escape_unit_name(&sample);
+        assert!(
+            escaped
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ":-_.\\".contains(c)),
+            "{:?} escaped to {:?}",
+            sample,
+            escaped
+        );
+        assert!(!escaped.starts_with('.'));
+        let unescaped = unescape_unit_name(&escaped).expect("unescape failed");
+        assert_eq!(sample, unescaped);
+    }
+}
+
+#[test]
+fn test_escape_path_round_trip() {
+    for sample in samples() {
+        let path = format!("/{}", sample);
+        let escaped = match escape_unit_path(&path) {
+            Ok(escaped) => escaped,
+            // paths with ".." components are rejected
+            Err(_) => {
+                assert!(path.split('/').any(|component| component == ".."));
+                continue;
+            }
+        };
+        let normalized: Vec<&str> = path
+            .split('/')
+            .filter(|component| !component.is_empty() && *component != ".")
+            .collect();
+        let expected = format!("/{}", normalized.join("/"));
+        let unescaped = unescape_unit_path(&escaped).expect("unescape path failed");
+        assert_eq!(expected, unescaped);
+    }
+}
+
+#[test]
+fn test_mangle() {
+    let mangled = |origin| {
+        mangle_unit_name(origin, UnitType::Service)
+            .expect("mangle failed")
+            .to_string()
+    };
+    assert_eq!(mangled("sshd.service"), "sshd.service");
+    assert_eq!(mangled("sshd"), "sshd.service");
+    assert_eq!(mangled("getty@tty1"), "getty@tty1.service");
+    assert_eq!(mangled("foo bar.socket"), "foo\\x20bar.socket");
+    assert_eq!(mangled("/dev/sda"), "dev-sda.device");
+    assert_eq!(mangled("/sys/devices/foo"), "sys-devices-foo.device");
+    assert_eq!(mangled("/home/foo"), "home-foo.mount");
+    assert_eq!(
+        mangle_unit_name("daily", UnitType::Timer)
+            .expect("mangle failed")
+            .to_string(),
+        "daily.timer"
+    );
+}
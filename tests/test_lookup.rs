@@ -0,0 +1,176 @@
+mod common;
+
+use common::TempDir;
+use std::path::{Path, PathBuf};
+use systemd_client::{LookupPaths, UnitName};
+
+fn write_file(root: &Path, path: &str, content: &str) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().expect("no parent directory"))
+        .expect("create directory failed");
+    std::fs::write(path, content).expect("write file failed");
+}
+
+fn mask_file(root: &Path, path: &str) {
+    let path = root.join(path);
+    std::fs::create_dir_all(path.parent().expect("no parent directory"))
+        .expect("create directory failed");
+    std::os::unix::fs::symlink("/dev/null", path).expect("create symlink failed");
+}
+
+#[test]
+fn test_load_effective_configuration() {
+    let temp_dir = TempDir::new("lookup");
+    let root = temp_dir.path();
+    write_file(
+        root,
+        "usr/test-app@.service",
+        "[Unit]\nDescription=test app %i\n\n[Service]\nExecStart=/usr/bin/test-app\nEnvironment=MODE=vendor\nRestart=no\n",
+    );
+    write_file(
+        root,
+        "usr/service.d/05-all.conf",
+        "[Unit]\nAfter=network.target\n",
+    );
+    // overridden by the same file name in etc
+    write_file(
+        root,
+        "usr/test-app@.service.d/10-override.conf",
+        "[Service]\nRestart=always\n",
+    );
+    write_file(
+        root,
+        "usr/test-app@.service.d/30-masked.conf",
+        "[Service]\nUser=nobody\n",
+    );
+    write_file(
+        root,
+        "etc/test-app@one.service.d/10-override.conf",
+        "[Service]\nEnvironment=\nEnvironment=MODE=etc\n",
+    );
+    mask_file(root, "etc/test-app@one.service.d/30-masked.conf");
+    // empty drop-ins are not masks, systemd lists them
+    write_file(root, "etc/test-app@one.service.d/40-empty.conf", "");
+    write_file(
+        root,
+        "run/test-.service.d/20-prefix.conf",
+        "[Service]\nNice=5\n",
+    );
+    write_file(
+        root,
+        "usr/masked.service",
+        "[Service]\nExecStart=/bin/true\n",
+    );
+    mask_file(root, "etc/masked.service");
+
+    let lookup_paths = LookupPaths::new(vec![root.join("etc"), root.join("run"), root.join("usr")]);
+    let unit_name: UnitName = "test-app@one.service".parse().expect("invalid unit name");
+    let configuration = lookup_paths
+        .load_effective_configuration(&unit_name)
+        .expect("load 'test-app@one.service' failed");
+    assert!(!configuration.masked);
+    assert_eq!(
+        Some(root.join("usr/test-app@.service")),
+        configuration.fragment
+    );
+    let drop_ins: Vec<PathBuf> = vec![
+        root.join("usr/service.d/05-all.conf"),
+        root.join("etc/test-app@one.service.d/10-override.conf"),
+        root.join("run/test-.service.d/20-prefix.conf"),
+        root.join("etc/test-app@one.service.d/40-empty.conf"),
+    ];
+    assert_eq!(drop_ins, configuration.drop_ins);
+    let document = &configuration.document;
+    assert_eq!(Some("no"), document.get("Service", "Restart"));
+    assert_eq!(vec!["MODE=etc"], document.get_all("Service", "Environment"));
+    assert_eq!(Some("5"), document.get("Service", "Nice"));
+    assert_eq!(None, document.get("Service", "User"));
+    assert_eq!(
+        Some(drop_ins[1].as_path()),
+        document.origin("Service", "Environment")
+    );
+    assert_eq!(
+        Some(root.join("usr/test-app@.service").as_path()),
+        document.origin("Service", "ExecStart")
+    );
+    assert_eq!(
+        Some(drop_ins[0].as_path()),
+        document.origin("Unit", "After")
+    );
+
+    let unit_name: UnitName = "masked.service".parse().expect("invalid unit name");
+    let configuration = lookup_paths
+        .load_effective_configuration(&unit_name)
+        .expect("load 'masked.service' failed");
+    assert!(configuration.masked);
+    assert!(configuration.document.sections().is_empty());
+
+    let unit_name: UnitName = "missing.service".parse().expect("invalid unit name");
+    let configuration = lookup_paths
+        .load_effective_configuration(&unit_name)
+        .expect("load 'missing.service' failed");
+    assert_eq!(None, configuration.fragment);
+}
+
+#[test]
+fn test_system_lookup_paths() {
+    let directories: Vec<PathBuf> = vec![
+        "/etc/systemd/system.control",
+        "/run/systemd/system.control",
+        "/run/systemd/transient",
+        "/run/systemd/generator.early",
+        "/etc/systemd/system",
+        "/etc/systemd/system.attached",
+        "/run/systemd/system",
+        "/run/systemd/system.attached",
+        "/run/systemd/generator",
+        "/usr/local/lib/systemd/system",
+        "/usr/lib/systemd/system",
+        "/lib/systemd/system",
+        "/run/systemd/generator.late",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+    assert_eq!(directories, LookupPaths::system().directories);
+}
+
+#[test]
+fn test_user_lookup_paths() {
+    std::env::set_var("HOME", "/home/test");
+    std::env::set_var("XDG_CONFIG_HOME", "relative/config");
+    std::env::set_var("XDG_DATA_HOME", "");
+    std::env::set_var("XDG_RUNTIME_DIR", "/run/user/1000");
+    std::env::set_var("XDG_CONFIG_DIRS", "/etc/xdg-a:relative:/etc/xdg-b");
+    std::env::remove_var("XDG_DATA_DIRS");
+    let directories: Vec<PathBuf> = vec![
+        "/home/test/.config/systemd/user.control",
+        "/run/user/1000/systemd/user.control",
+        "/run/user/1000/systemd/transient",
+        "/run/user/1000/systemd/generator.early",
+        "/home/test/.config/systemd/user",
+        "/etc/xdg-a/systemd/user",
+        "/etc/xdg-b/systemd/user",
+        "/etc/systemd/user",
+        "/run/user/1000/systemd/user",
+        "/run/systemd/user",
+        "/run/user/1000/systemd/generator",
+        "/home/test/.local/share/systemd/user",
+        "/usr/local/share/systemd/user",
+        "/usr/share/systemd/user",
+        "/usr/local/lib/systemd/user",
+        "/usr/lib/systemd/user",
+        "/run/user/1000/systemd/generator.late",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .collect();
+    assert_eq!(directories, LookupPaths::user().directories);
+
+    // no runtime directories without $XDG_RUNTIME_DIR
+    std::env::set_var("XDG_RUNTIME_DIR", "");
+    assert!(LookupPaths::user()
+        .directories
+        .iter()
+        .all(|directory| !directory.starts_with("/run/user")));
+}
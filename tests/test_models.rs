@@ -0,0 +1,57 @@
+mod common;
+
+use common::unit;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use systemd_client::{automount, boot_time, mount, swap, TimerProps};
+
+fn timer_props(next_elapse_realtime: u64, next_elapse_monotonic: u64) -> TimerProps {
+    TimerProps::builder()
+        .unit(String::from("test.service"))
+        .timers_monotonic(vec![])
+        .timers_calendar(vec![])
+        .next_elapse_realtime(next_elapse_realtime)
+        .next_elapse_monotonic(next_elapse_monotonic)
+        .last_trigger(0)
+        .result(String::from("success"))
+        .persistent(false)
+        .build()
+}
+
+#[test]
+fn test_timer_next_elapse() {
+    let boot_time = UNIX_EPOCH + Duration::from_secs(1_000_000);
+    // OnBootSec= and OnUnitActiveSec= timers only have a monotonic elapse
+    let monotonic = timer_props(0, 60_000_000);
+    assert_eq!(
+        Some(boot_time + Duration::from_secs(60)),
+        monotonic.next_elapse(boot_time)
+    );
+    let calendar = timer_props(2_000_000_000_000, u64::MAX);
+    assert_eq!(
+        Some(UNIX_EPOCH + Duration::from_secs(2_000_000)),
+        calendar.next_elapse(boot_time)
+    );
+    // the earlier of both fires first
+    let both = timer_props(1_000_030_000_000, 60_000_000);
+    assert_eq!(
+        Some(boot_time + Duration::from_secs(30)),
+        both.next_elapse(boot_time)
+    );
+    assert_eq!(None, timer_props(0, 0).next_elapse(boot_time));
+}
+
+#[test]
+fn test_boot_time() {
+    let boot_time = boot_time().expect("read boot time failed");
+    assert!(boot_time > UNIX_EPOCH);
+    assert!(boot_time <= SystemTime::now());
+}
+
+#[test]
+fn test_typed_proxy_from_unit() {
+    // the unit type is checked before connecting to the bus
+    let service = unit("test.service");
+    assert!(mount::build_blocking_proxy_from_unit(&service).is_err());
+    assert!(automount::build_blocking_proxy_from_unit(&service).is_err());
+    assert!(swap::build_blocking_proxy_from_unit(&service).is_err());
+}
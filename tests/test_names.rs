@@ -0,0 +1,97 @@
+mod common;
+
+use common::unit;
+use systemd_client::{group_units_by_template, UnitName, UnitType};
+
+#[test]
+fn test_parse_unit_name() {
+    let name: UnitName = "sshd.service".parse().expect("parse 'sshd.service' failed");
+    assert_eq!(name.prefix(), "sshd");
+    assert_eq!(name.instance(), None);
+    assert_eq!(name.unit_type(), UnitType::Service);
+    assert!(!name.is_template());
+    assert_eq!(name.to_string(), "sshd.service");
+
+    let name: UnitName = "getty@.service"
+        .parse()
+        .expect("parse 'getty@.service' failed");
+    assert_eq!(name.prefix(), "getty");
+    assert!(name.is_template());
+    assert!(!name.is_instance());
+
+    let name: UnitName = "getty@tty1.service"
+        .parse()
+        .expect("parse 'getty@tty1.service' failed");
+    assert_eq!(name.prefix(), "getty");
+    assert_eq!(name.instance(), Some("tty1"));
+
+    let name: UnitName = "dev-disk-by\\x2duuid-1234.device"
+        .parse()
+        .expect("parse device unit name failed");
+    assert_eq!(name.unit_type(), UnitType::Device);
+
+    let name: UnitName = "system-getty.slice".parse().expect("parse slice failed");
+    assert_eq!(name.unit_type(), UnitType::Slice);
+}
+
+#[test]
+fn test_invalid_unit_name() {
+    for name in [
+        "",
+        "sshd",
+        "sshd.unknown",
+        ".service",
+        "@tty1.service",
+        "foo bar.service",
+        "foo/bar.service",
+        "foo@tty 1.service",
+    ] {
+        assert!(
+            name.parse::<UnitName>().is_err(),
+            "{:?} should be invalid",
+            name
+        );
+    }
+    let long_name = format!("{}.service", "a".repeat(248));
+    assert!(long_name.parse::<UnitName>().is_err());
+    let max_name = format!("{}.service", "a".repeat(247));
+    assert!(max_name.parse::<UnitName>().is_ok());
+}
+
+#[test]
+fn test_template_instance() {
+    let template: UnitName = "worker@.service".parse().expect("parse template failed");
+    let instance = template
+        .instantiate("queue/high priority")
+        .expect("instantiate failed");
+    assert_eq!(instance.as_str(), "worker@queue-high\\x20priority.service");
+    assert_eq!(instance.template(), Some(template.clone()));
+    let (split_template, split_instance) = instance.split_instance().expect("split failed");
+    assert_eq!(split_template, template);
+    assert_eq!(split_instance, "queue/high priority");
+    assert!(template.instantiate("").is_err());
+
+    let plain: UnitName = "worker.service".parse().expect("parse plain failed");
+    assert!(plain.instantiate("1").is_err());
+    assert!(plain.split_instance().is_err());
+    assert!(template.split_instance().is_err());
+    assert_eq!(plain.template(), None);
+}
+
+#[test]
+fn test_group_units_by_template() {
+    let units = vec![
+        unit("worker@1.service"),
+        unit("sshd.service"),
+        unit("worker@2.service"),
+        unit("getty@tty1.service"),
+    ];
+    let groups = group_units_by_template(units);
+    assert_eq!(groups.len(), 2);
+    let worker: UnitName = "worker@.service".parse().expect("parse template failed");
+    let names: Vec<&str> = groups[&worker]
+        .iter()
+        .map(|unit| unit.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["worker@1.service", "worker@2.service"]);
+}
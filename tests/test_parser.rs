@@ -0,0 +1,200 @@
+mod common;
+
+use common::read_resource;
+use systemd_client::{DocumentLine, ServiceUnitConfiguration, UnitDocument};
+
+#[test]
+fn test_parse_document() {
+    let content = read_resource("vendor.service");
+    let document = UnitDocument::parse(&content).expect("parse 'vendor.service' failed");
+    assert_eq!(3, document.preamble().len());
+    let names: Vec<&str> = document.sections().iter().map(|s| s.name()).collect();
+    assert_eq!(vec!["Unit", "Service", "Install"], names);
+    assert_eq!(Some("vendor daemon"), document.get("Unit", "Description"));
+    // the empty assignment drops the earlier After= lines
+    assert_eq!(
+        vec!["network-online.target"],
+        document.get_all("Unit", "After")
+    );
+    assert_eq!(
+        Some("/usr/bin/vendord  --foreground  --config /etc/vendord.conf"),
+        document.get("Service", "ExecStart")
+    );
+    assert_eq!(Some("on-failure"), document.get("Service", "Restart"));
+    assert_eq!(None, document.get("Service", "User"));
+    let exec_start = document
+        .section("Service")
+        .expect("no [Service] section")
+        .directives()
+        .find(|directive| directive.key() == "ExecStart")
+        .expect("no ExecStart=");
+    assert_eq!(Some(16), exec_start.line());
+    // round trip keeps comments, continuations and odd spacing
+    assert_eq!(content, document.to_string());
+    let content = "[Unit]\r\nDescription=crlf\r\n\r\n[Service]\r\nExecStart=/bin/true";
+    let document = UnitDocument::parse(content).expect("parse crlf content failed");
+    assert_eq!(Some("crlf"), document.get("Unit", "Description"));
+    assert_eq!(content, document.to_string());
+}
+
+#[test]
+fn test_parse_errors() {
+    let err = UnitDocument::parse("Description=orphan\n")
+        .err()
+        .expect("assignment outside of section accepted");
+    assert!(err.to_string().contains("line 1"));
+    let err = UnitDocument::parse("[Unit]\n\nDescription\n")
+        .err()
+        .expect("missing '=' accepted");
+    assert!(err.to_string().contains("line 3"));
+    assert!(UnitDocument::parse("[Unit\n").is_err());
+}
+
+#[test]
+fn test_edit_document() {
+    let mut document = UnitDocument::parse(&read_resource("vendor.service"))
+        .expect("parse 'vendor.service' failed");
+    document.set("Unit", "After", "network.target");
+    document.set("Service", "Restart", "always");
+    document.append("Service", "Environment", "DEBUG=1");
+    assert!(document.remove("Service", "ExecStart"));
+    assert!(!document.remove("Service", "ExecStart"));
+    document.set("Service", "ExecStart", "/usr/bin/vendord");
+    document.set("X-Vendor", "Option", "value");
+    assert!(document.remove_section("Install"));
+    let section = document.section_mut("Unit").expect("no [Unit] section");
+    section
+        .lines_mut()
+        .insert(0, DocumentLine::Comment("# edited".to_owned()));
+    let expected = r#"#  SPDX-License-Identifier: LGPL-2.1-or-later
+#  vendor unit with the syntax systemd accepts
+
+[Unit]
+# edited
+Description=vendor daemon
+Documentation=man:vendord(8)
+After=network.target
+; dependencies above are reset below
+
+[Service]
+Type=notify
+Environment=LANG=C "TZ=UTC"
+Restart=always
+Environment=DEBUG=1
+ExecStart=/usr/bin/vendord
+
+[X-Vendor]
+Option=value
+"#;
+    assert_eq!(expected, document.to_string());
+    let mut document = UnitDocument::new();
+    document.set("Unit", "Description", "new");
+    document.set("Service", "ExecStart", "/bin/true");
+    assert_eq!(
+        "[Unit]\nDescription=new\n\n[Service]\nExecStart=/bin/true\n",
+        document.to_string()
+    );
+}
+
+#[test]
+fn test_document_into_service_unit() {
+    for name in ["test.service", "test_oneshot.service"] {
+        let content = read_resource(name);
+        let document = UnitDocument::parse(&content).expect("parse unit file failed");
+        let svc_unit = ServiceUnitConfiguration::try_from(&document)
+            .unwrap_or_else(|err| panic!("convert '{}' failed: {}", name, err));
+        assert_eq!(content, svc_unit.to_string());
+    }
+    let document = UnitDocument::parse(&read_resource("vendor.service"))
+        .expect("parse 'vendor.service' failed");
+    let svc_unit =
+        ServiceUnitConfiguration::try_from(&document).expect("convert 'vendor.service' failed");
+    assert_eq!(
+        vec![
+            "/usr/bin/vendord",
+            "--foreground",
+            "--config",
+            "/etc/vendord.conf"
+        ],
+        svc_unit.service().exec_start
+    );
+    assert_eq!(vec!["network-online.target"], svc_unit.unit.after);
+    assert_eq!(2, svc_unit.service().envs.len());
+    assert_eq!("TZ", svc_unit.service().envs[1].key);
+    for content in [
+        "[Service]\nExecStart=/usr/bin/getty %I\n",
+        "[Service]\nExecStart=/bin/sh -c \"echo hi\"\n",
+        "[Service]\nProtectSystem=bogus\n",
+        "[Service]\nStandardOutput=fd:log\n",
+        "[Service]\nDeviceAllow=/dev/null r\n",
+        "[Unit]\nConditionFirstBoot=yes\n",
+        "[Service]\nType=bogus\n",
+        "[Timer]\nOnCalendar=daily\n",
+    ] {
+        let document = UnitDocument::parse(content).expect("parse unit file failed");
+        assert!(
+            ServiceUnitConfiguration::try_from(&document).is_err(),
+            "{:?} converted",
+            content
+        );
+    }
+}
+
+#[test]
+fn test_document_sub_configurations_into_service_unit() {
+    let content = "[Unit]\n\
+                   ConditionPathExists=|!/etc/vendord.conf\n\
+                   AssertUser=vendor\n\
+                   [Service]\n\
+                   ExecStart=/usr/bin/vendord\n\
+                   ProtectSystem=strict\n\
+                   ProtectHome=read-only\n\
+                   NoNewPrivileges=yes\n\
+                   SystemCallFilter=@system-service\n\
+                   SystemCallFilter=~@privileged @resources\n\
+                   RestrictAddressFamilies=AF_UNIX AF_INET\n\
+                   MemoryMax=512M\n\
+                   TasksMax=50%\n\
+                   CPUQuota=150%\n\
+                   IOReadBandwidthMax=/dev/sda 1M\n\
+                   LimitNOFILE=1024:4096\n\
+                   UMask=0027\n\
+                   EnvironmentFile=-/etc/default/vendord\n\
+                   StandardOutput=append:/var/log/vendord.log\n\
+                   LoadCredential=token:/etc/vendord/token\n";
+    let document = UnitDocument::parse(content).expect("parse unit file failed");
+    let svc_unit = ServiceUnitConfiguration::try_from(&document).expect("convert unit file failed");
+    assert_eq!(1, svc_unit.unit.conditions.len());
+    assert!(svc_unit.unit.conditions[0].trigger);
+    assert!(svc_unit.unit.conditions[0].negate);
+    assert_eq!("/etc/vendord.conf", svc_unit.unit.conditions[0].value);
+    assert_eq!("vendor", svc_unit.unit.asserts[0].value);
+    let rendered = svc_unit.to_string();
+    for line in [
+        "ConditionPathExists=|!/etc/vendord.conf",
+        "AssertUser=vendor",
+        "ProtectSystem=strict",
+        "ProtectHome=read-only",
+        "NoNewPrivileges=yes",
+        "SystemCallFilter=@system-service",
+        "SystemCallFilter=~@privileged @resources",
+        "RestrictAddressFamilies=AF_UNIX",
+        "RestrictAddressFamilies=AF_INET",
+        "MemoryMax=512M",
+        "TasksMax=50%",
+        "CPUQuota=150%",
+        "IOReadBandwidthMax=/dev/sda 1M",
+        "LimitNOFILE=1024:4096",
+        "UMask=0027",
+        "EnvironmentFile=-/etc/default/vendord",
+        "StandardOutput=append:/var/log/vendord.log",
+        "LoadCredential=token:/etc/vendord/token",
+    ] {
+        assert!(
+            rendered.lines().any(|rendered| rendered == line),
+            "{:?} missing in {:?}",
+            line,
+            rendered
+        );
+    }
+}
@@ -1,4 +1,12 @@
-use systemd_client::{ServiceConfiguration, ServiceUnitConfiguration, UnitConfiguration};
+use systemd_client::{
+    escape_specifiers, AddressFamily, AutomountConfiguration, ByteSize, Condition, ConditionType,
+    DropInConfiguration, ExecConfiguration, InstallConfiguration, KillMode, Limit, Listen,
+    MountConfiguration, NotifyAccess, PathCondition, PathConfiguration, ProtectHome, ProtectSystem,
+    ResourceControlConfiguration, RestartPolicy, SecurityConfiguration, ServiceConfiguration,
+    ServiceType, ServiceUnitConfiguration, SliceConfiguration, SocketActivation, SocketAddress,
+    SocketConfiguration, SocketUnitConfiguration, StandardOutput, TasksMax, TimerConfiguration,
+    TimerUnitConfiguration, UnitConfiguration, UnitFile, UnitType,
+};
 
 #[test]
 fn test_service_template() {
@@ -20,3 +28,680 @@ fn test_service_template() {
     let expected_svc_unit = String::from_utf8(buffer).expect("invalid utf-8 in 'test.service'");
     assert_eq!(expected_svc_unit, actual_svc_unit);
 }
+
+#[test]
+fn test_oneshot_service_template() {
+    let unit_builder = UnitConfiguration::builder().description("test oneshot service");
+    let svc_builder = ServiceConfiguration::builder()
+        .ty(ServiceType::Oneshot)
+        .pid_file("/run/test.pid")
+        .remain_after_exit(true)
+        .exec_start_pre(vec!["/bin/mkdir", "-p", "/run/test"])
+        .exec_start_pre(vec!["/bin/touch", "/run/test/ready"])
+        .exec_start(vec!["/bin/echo", "aloha"])
+        .exec_start_post(vec!["/bin/echo", "started"])
+        .exec_reload(vec!["/bin/kill", "-HUP", "${MAINPID}"])
+        .exec_stop(vec!["/bin/echo", "stopping"])
+        .exec_stop_post(vec!["/bin/rm", "-rf", "/run/test"])
+        .restart_policy(RestartPolicy::OnFailure)
+        .restart_sec("5s")
+        .timeout_start_sec("30s")
+        .timeout_stop_sec("1min 30s")
+        .success_exit_status("75")
+        .success_exit_status("SIGHUP")
+        .kill_mode(KillMode::Mixed)
+        .kill_signal("SIGINT")
+        .notify_access(NotifyAccess::Main);
+    let svc_unit = ServiceUnitConfiguration::builder()
+        .unit(unit_builder)
+        .service(svc_builder)
+        .build();
+    let actual_svc_unit = format!("{}", svc_unit);
+    let buffer = std::fs::read("tests/resources/test_oneshot.service")
+        .expect("open file 'test_oneshot.service' failed");
+    let expected_svc_unit =
+        String::from_utf8(buffer).expect("invalid utf-8 in 'test_oneshot.service'");
+    assert_eq!(expected_svc_unit, actual_svc_unit);
+}
+
+#[test]
+fn test_unit_dependencies() {
+    let unit = UnitConfiguration::builder()
+        .description("test dependencies")
+        .documentation("man:test(8)")
+        .documentation("https://example.com/test")
+        .requires("network-online.target")
+        .wants("network-online.target")
+        .binds_to("dev-sda.device")
+        .part_of("test.target")
+        .conflicts("shutdown.target")
+        .before("shutdown.target")
+        .after("network-online.target")
+        .after("dev-sda.device")
+        .on_failure("notify-failure@%n.service")
+        .start_limit_interval_sec("10s")
+        .start_limit_burst(5)
+        .default_dependencies(false)
+        .stop_when_unneeded(true)
+        .build();
+    let expected = "[Unit]
+Description=test dependencies
+Documentation=man:test(8) https://example.com/test
+Requires=network-online.target
+Wants=network-online.target
+BindsTo=dev-sda.device
+PartOf=test.target
+Conflicts=shutdown.target
+Before=shutdown.target
+After=network-online.target dev-sda.device
+OnFailure=notify-failure@%n.service
+StartLimitIntervalSec=10s
+StartLimitBurst=5
+DefaultDependencies=no
+StopWhenUnneeded=yes
+";
+    assert_eq!(expected, unit.to_string());
+    assert_eq!("[Unit]\n", UnitConfiguration::builder().build().to_string());
+}
+
+#[test]
+fn test_unit_conditions() {
+    let unit = UnitConfiguration::builder()
+        .description("bare metal only")
+        .condition(
+            Condition::builder()
+                .ty(ConditionType::Virtualization)
+                .value("no")
+                .build(),
+        )
+        .condition(
+            Condition::builder()
+                .ty(ConditionType::PathExists)
+                .value("/etc/test.conf")
+                .trigger()
+                .build(),
+        )
+        .condition(
+            Condition::builder()
+                .ty(ConditionType::FileNotEmpty)
+                .value("/etc/test.d/override.conf")
+                .trigger()
+                .negate()
+                .build(),
+        )
+        .assert(
+            Condition::builder()
+                .ty(ConditionType::Architecture)
+                .value("x86-64")
+                .build(),
+        )
+        .assert(
+            Condition::builder()
+                .ty(ConditionType::User)
+                .value("root")
+                .negate()
+                .build(),
+        )
+        .build();
+    let expected = "[Unit]
+Description=bare metal only
+ConditionVirtualization=no
+ConditionPathExists=|/etc/test.conf
+ConditionFileNotEmpty=|!/etc/test.d/override.conf
+AssertArchitecture=x86-64
+AssertUser=!root
+";
+    assert_eq!(expected, unit.to_string());
+}
+
+#[test]
+fn test_install_section() {
+    let install = InstallConfiguration::builder()
+        .wanted_by("graphical.target")
+        .required_by("test.target")
+        .alias("alias.service")
+        .also("test.socket")
+        .default_instance("default")
+        .build();
+    let expected = "[Install]
+WantedBy=graphical.target
+RequiredBy=test.target
+Alias=alias.service
+Also=test.socket
+DefaultInstance=default
+";
+    assert_eq!(expected, install.to_string());
+
+    // a standalone section keeps the multi-user.target fallback
+    assert_eq!(
+        "[Install]\nWantedBy=multi-user.target\n",
+        InstallConfiguration::builder().build().to_string()
+    );
+    let install = InstallConfiguration::builder()
+        .default_wanted_by("default.target")
+        .build();
+    assert_eq!("[Install]\nWantedBy=default.target\n", install.to_string());
+    let install = InstallConfiguration::builder()
+        .default_wanted_by("default.target")
+        .clear_default_wanted_by()
+        .build();
+    assert!(install.is_empty());
+    assert_eq!("", install.to_string());
+
+    let svc_unit = ServiceUnitConfiguration::builder()
+        .unit(UnitConfiguration::builder().description("no install"))
+        .service(ServiceConfiguration::builder().exec_start(vec!["/bin/true"]))
+        .install(InstallConfiguration::builder().clear_default_wanted_by())
+        .build();
+    assert!(svc_unit.to_string().ends_with("RestartSec=100ms\n"));
+}
+
+#[test]
+fn test_service_security() {
+    let security = SecurityConfiguration::builder()
+        .protect_system(ProtectSystem::Strict)
+        .protect_home(ProtectHome::ReadOnly)
+        .private_tmp(true)
+        .private_devices(true)
+        .no_new_privileges(true)
+        .dynamic_user(true)
+        .capability_bounding_set("CAP_NET_BIND_SERVICE")
+        .capability_bounding_set("~CAP_SYS_ADMIN")
+        .ambient_capabilities("CAP_NET_BIND_SERVICE")
+        .read_write_paths("/var/lib/test")
+        .inaccessible_paths("/root")
+        .restrict_address_families(AddressFamily::Unix)
+        .restrict_address_families(AddressFamily::Inet)
+        .restrict_address_families(AddressFamily::Inet6)
+        .restrict_address_families(AddressFamily::Other("~AF_PACKET"))
+        .system_call_filter("@system-service")
+        .system_call_filter("~@privileged")
+        .protect_kernel_tunables(true)
+        .protect_kernel_modules(true)
+        .protect_kernel_logs(true)
+        .memory_deny_write_execute(true);
+    let service = ServiceConfiguration::builder()
+        .exec_start(vec!["/usr/bin/test"])
+        .security(security)
+        .build();
+    let expected = "[Service]
+Type=simple
+ExecStart=/usr/bin/test
+Restart=no
+RestartSec=100ms
+ProtectSystem=strict
+ProtectHome=read-only
+PrivateTmp=yes
+PrivateDevices=yes
+NoNewPrivileges=yes
+DynamicUser=yes
+CapabilityBoundingSet=CAP_NET_BIND_SERVICE
+CapabilityBoundingSet=~CAP_SYS_ADMIN
+AmbientCapabilities=CAP_NET_BIND_SERVICE
+ReadWritePaths=/var/lib/test
+InaccessiblePaths=/root
+RestrictAddressFamilies=AF_UNIX
+RestrictAddressFamilies=AF_INET
+RestrictAddressFamilies=AF_INET6
+RestrictAddressFamilies=~AF_PACKET
+SystemCallFilter=@system-service
+SystemCallFilter=~@privileged
+ProtectKernelTunables=yes
+ProtectKernelModules=yes
+ProtectKernelLogs=yes
+MemoryDenyWriteExecute=yes
+";
+    assert_eq!(expected, service.to_string());
+}
+
+#[test]
+fn test_byte_size() {
+    assert_eq!("512", ByteSize::Bytes(512).to_string());
+    assert_eq!("1536", ByteSize::Bytes(1536).to_string());
+    assert_eq!("4K", ByteSize::Bytes(4096).to_string());
+    assert_eq!("1536M", ByteSize::mebibytes(1536).to_string());
+    assert_eq!("2G", ByteSize::gibibytes(2).to_string());
+    assert_eq!("1T", ByteSize::gibibytes(1024).to_string());
+    assert_eq!("0", ByteSize::Bytes(0).to_string());
+    assert_eq!("infinity", ByteSize::Infinity.to_string());
+    assert_eq!(ByteSize::Bytes(1 << 63), ByteSize::tebibytes(1 << 23));
+    assert_eq!(ByteSize::Infinity, ByteSize::tebibytes(1 << 24));
+    assert_eq!(ByteSize::Infinity, ByteSize::kibibytes(u64::MAX));
+}
+
+#[test]
+fn test_service_resource_control() {
+    let resource_control = ResourceControlConfiguration::builder()
+        .slice("tenant-a.slice")
+        .cpu_weight(200)
+        .cpu_quota(150)
+        .allowed_cpus("0-3")
+        .memory_high(ByteSize::mebibytes(512))
+        .memory_max(ByteSize::gibibytes(1))
+        .memory_swap_max(ByteSize::Infinity)
+        .tasks_max(TasksMax::Count(64))
+        .io_weight(50)
+        .io_device_weight("/dev/sda", 200)
+        .io_read_bandwidth_max("/dev/sda", ByteSize::mebibytes(10))
+        .ip_address_allow("10.0.0.0/8")
+        .ip_address_deny("any")
+        .delegate(true);
+    let service = ServiceConfiguration::builder()
+        .exec_start(vec!["/usr/bin/test"])
+        .resource_control(resource_control)
+        .build();
+    let expected = "[Service]
+Type=simple
+ExecStart=/usr/bin/test
+Restart=no
+RestartSec=100ms
+Slice=tenant-a.slice
+CPUWeight=200
+CPUQuota=150%
+AllowedCPUs=0-3
+MemoryHigh=512M
+MemoryMax=1G
+MemorySwapMax=infinity
+TasksMax=64
+IOWeight=50
+IODeviceWeight=/dev/sda 200
+IOReadBandwidthMax=/dev/sda 10M
+IPAddressAllow=10.0.0.0/8
+IPAddressDeny=any
+Delegate=yes
+";
+    assert_eq!(expected, service.to_string());
+}
+
+#[test]
+fn test_service_exec() {
+    let exec = ExecConfiguration::builder()
+        .limit_nofile(Limit::Value(1024), Limit::Value(524288))
+        .limit_nproc(Limit::Value(512), Limit::Value(512))
+        .limit_core(Limit::Infinity, Limit::Infinity)
+        .nice(-5)
+        .oom_score_adjust(-100)
+        .umask(0o027)
+        .environment_file("/etc/default/test", true)
+        .environment_file("/etc/test/env", false)
+        .standard_output(StandardOutput::Journal)
+        .standard_error(StandardOutput::Append("/var/log/test.err"))
+        .syslog_identifier("test")
+        .load_credential("tls.key", "/etc/test/tls.key")
+        .set_credential("mode", "production")
+        .state_directory("test")
+        .state_directory_mode(0o750)
+        .runtime_directory("test")
+        .logs_directory("test")
+        .cache_directory("test")
+        .cache_directory_mode(0o700);
+    let service = ServiceConfiguration::builder()
+        .exec_start(vec!["/usr/bin/test"])
+        .exec(exec)
+        .build();
+    let expected = "[Service]
+Type=simple
+ExecStart=/usr/bin/test
+Restart=no
+RestartSec=100ms
+LimitNOFILE=1024:524288
+LimitNPROC=512
+LimitCORE=infinity
+Nice=-5
+OOMScoreAdjust=-100
+UMask=0027
+EnvironmentFile=-/etc/default/test
+EnvironmentFile=/etc/test/env
+StandardOutput=journal
+StandardError=append:/var/log/test.err
+SyslogIdentifier=test
+LoadCredential=tls.key:/etc/test/tls.key
+SetCredential=mode:production
+StateDirectory=test
+StateDirectoryMode=0750
+RuntimeDirectory=test
+LogsDirectory=test
+CacheDirectory=test
+CacheDirectoryMode=0700
+";
+    assert_eq!(expected, service.to_string());
+}
+
+#[test]
+fn test_timer_template() {
+    let unit_builder = UnitConfiguration::builder().description("run test daily");
+    let timer_builder = TimerConfiguration::builder()
+        .on_calendar("*-*-* 04:00:00")
+        .on_boot_sec("15min")
+        .accuracy_sec("1min")
+        .randomized_delay_sec("30min")
+        .persistent(true)
+        .wake_system(false)
+        .unit("test.service");
+    // a fresh [Install] builder keeps the timers.target default
+    let timer_unit = TimerUnitConfiguration::builder()
+        .unit(unit_builder)
+        .timer(timer_builder)
+        .install(InstallConfiguration::builder())
+        .build();
+    let actual_timer_unit = format!("{}", timer_unit);
+    let buffer =
+        std::fs::read("tests/resources/test.timer").expect("open file 'test.timer' failed");
+    let expected_timer_unit = String::from_utf8(buffer).expect("invalid utf-8 in 'test.timer'");
+    assert_eq!(expected_timer_unit, actual_timer_unit);
+}
+
+#[test]
+fn test_socket_template() {
+    let unit_builder = UnitConfiguration::builder().description("test socket");
+    let socket_builder = SocketConfiguration::builder()
+        .listen(Listen::Stream(SocketAddress::Port(8080)))
+        .listen(Listen::Stream(SocketAddress::Inet(
+            "127.0.0.1:8081".parse().expect("invalid socket address"),
+        )))
+        .listen(Listen::Stream(SocketAddress::Inet(
+            "[::1]:8082".parse().expect("invalid socket address"),
+        )))
+        .listen(Listen::Datagram(SocketAddress::Unix("/run/test.sock")))
+        .listen(Listen::SequentialPacket(SocketAddress::Abstract("test")))
+        .listen(Listen::Fifo("/run/test.fifo"))
+        .listen(Listen::Netlink("kobject-uevent", Some(1)))
+        .accept(false)
+        .socket_user("test")
+        .socket_group("test")
+        .socket_mode(0o660)
+        .backlog(128)
+        .file_descriptor_name("test")
+        .service("test.service");
+    let socket_unit = SocketUnitConfiguration::builder()
+        .unit(unit_builder)
+        .socket(socket_builder)
+        .install(InstallConfiguration::builder())
+        .build();
+    let actual_socket_unit = format!("{}", socket_unit);
+    let buffer =
+        std::fs::read("tests/resources/test.socket").expect("open file 'test.socket' failed");
+    let expected_socket_unit = String::from_utf8(buffer).expect("invalid utf-8 in 'test.socket'");
+    assert_eq!(expected_socket_unit, actual_socket_unit);
+    let scoped = std::net::SocketAddrV6::new("fe80::1".parse().expect("invalid address"), 80, 0, 2);
+    assert_eq!(
+        "[fe80::1%%2]:80",
+        SocketAddress::Inet(scoped.into()).to_string()
+    );
+}
+
+#[test]
+fn test_socket_activation() {
+    let socket = |accept: bool, service: Option<&'static str>| {
+        let mut socket_builder = SocketConfiguration::builder()
+            .listen(Listen::Stream(SocketAddress::Port(7)))
+            .accept(accept);
+        if let Some(service) = service {
+            socket_builder = socket_builder.service(service);
+        }
+        SocketUnitConfiguration::builder()
+            .socket(socket_builder)
+            .build()
+    };
+    let service = || {
+        ServiceUnitConfiguration::builder()
+            .service(ServiceConfiguration::builder().exec_start(vec!["/usr/bin/echod"]))
+            .build()
+    };
+    // one service instance per connection
+    let activation = SocketActivation::new("echo.socket", socket(true, None), service())
+        .expect("pair accepting socket failed");
+    assert_eq!("echo@.service", activation.service_name().as_str());
+    let files = activation.render();
+    assert_eq!("echo.socket", files[0].0.as_str());
+    assert!(files[0].1.contains("Accept=yes\n"));
+    assert_eq!("echo@.service", files[1].0.as_str());
+    assert!(files[1].1.contains("ExecStart=/usr/bin/echod\n"));
+    let activation = SocketActivation::new("echo.socket", socket(false, None), service())
+        .expect("pair socket failed");
+    assert_eq!("echo.service", activation.service_name().as_str());
+    let activation = SocketActivation::new(
+        "echo.socket",
+        socket(false, Some("echod.service")),
+        service(),
+    )
+    .expect("pair socket with Service= failed");
+    assert_eq!("echod.service", activation.service_name().as_str());
+
+    assert!(SocketActivation::new("echo.service", socket(false, None), service()).is_err());
+    assert!(SocketActivation::new(
+        "echo.socket",
+        socket(true, Some("echod.service")),
+        service()
+    )
+    .is_err());
+    assert!(
+        SocketActivation::new("echo.socket", socket(false, Some("echo.timer")), service()).is_err()
+    );
+    assert!(SocketActivation::new(
+        "echo.socket",
+        socket(false, Some("echo@.service")),
+        service()
+    )
+    .is_err());
+}
+
+#[test]
+fn test_mount_template() {
+    let unit_builder = UnitConfiguration::builder().description("test mount");
+    let mount_builder = MountConfiguration::builder()
+        .what("/dev/disk/by-label/data")
+        .where_("/mnt/test data")
+        .ty("ext4")
+        .option("noatime")
+        .option("nodev")
+        .directory_mode(0o755)
+        .timeout_sec("30s");
+    let install_builder = InstallConfiguration::builder().wanted_by("local-fs.target");
+    let mount_unit: UnitFile<MountConfiguration> = UnitFile::builder()
+        .unit(unit_builder)
+        .section(mount_builder)
+        .install(install_builder)
+        .build();
+    assert_eq!(UnitType::Mount, mount_unit.unit_type());
+    let unit_name = mount_unit.unit_name().expect("invalid mount point");
+    assert_eq!("mnt-test\\x20data.mount", unit_name.as_str());
+    let relative = MountConfiguration::builder()
+        .where_("mnt/test")
+        .build_drop_in();
+    assert!(relative.unit_name().is_err());
+    let actual_mount_unit = format!("{}", mount_unit);
+    let buffer =
+        std::fs::read("tests/resources/test.mount").expect("open file 'test.mount' failed");
+    let expected_mount_unit = String::from_utf8(buffer).expect("invalid utf-8 in 'test.mount'");
+    assert_eq!(expected_mount_unit, actual_mount_unit);
+}
+
+#[test]
+#[should_panic(expected = "where must be an absolute path")]
+fn test_mount_relative_where() {
+    MountConfiguration::builder()
+        .what("/dev/sdb1")
+        .where_("mnt/test")
+        .build();
+}
+
+#[test]
+fn test_path_template() {
+    let unit_builder = UnitConfiguration::builder().description("watch test spool");
+    let path_builder = PathConfiguration::builder()
+        .condition(PathCondition::PathExists("/var/spool/test/ready"))
+        .condition(PathCondition::DirectoryNotEmpty("/var/spool/test"))
+        .unit("test.service")
+        .make_directory(true)
+        .directory_mode(0o700);
+    let path_unit: UnitFile<PathConfiguration> = UnitFile::builder()
+        .unit(unit_builder)
+        .section(path_builder)
+        .install(InstallConfiguration::builder())
+        .build();
+    let actual_path_unit = format!("{}", path_unit);
+    let buffer = std::fs::read("tests/resources/test.path").expect("open file 'test.path' failed");
+    let expected_path_unit = String::from_utf8(buffer).expect("invalid utf-8 in 'test.path'");
+    assert_eq!(expected_path_unit, actual_path_unit);
+}
+
+#[test]
+fn test_automount_and_slice_template() {
+    let automount_unit: UnitFile<AutomountConfiguration> = UnitFile::builder()
+        .unit(UnitConfiguration::builder().description("test automount"))
+        .section(
+            AutomountConfiguration::builder()
+                .where_("/mnt/test")
+                .timeout_idle_sec("5min"),
+        )
+        .build();
+    assert_eq!(
+        "mnt-test.automount",
+        automount_unit
+            .unit_name()
+            .expect("invalid mount point")
+            .as_str()
+    );
+    assert_eq!(
+        "[Unit]\nDescription=test automount\n\n[Automount]\nWhere=/mnt/test\nTimeoutIdleSec=5min\n",
+        format!("{}", automount_unit)
+    );
+    let slice_unit: UnitFile<SliceConfiguration> = UnitFile::builder()
+        .unit(UnitConfiguration::builder().description("test slice"))
+        .section(
+            SliceConfiguration::builder().resource_control(
+                // a slice cannot be placed in another slice, Slice= is left out
+                ResourceControlConfiguration::builder()
+                    .slice("parent.slice")
+                    .cpu_weight(50)
+                    .memory_max(ByteSize::gibibytes(2)),
+            ),
+        )
+        .build();
+    assert_eq!(UnitType::Slice, slice_unit.unit_type());
+    assert_eq!(
+        "[Unit]\nDescription=test slice\n\n[Slice]\nCPUWeight=50\nMemoryMax=2G\n",
+        format!("{}", slice_unit)
+    );
+}
+
+#[test]
+fn test_service_quoting() {
+    let unit_builder = UnitConfiguration::builder().description("100% hostile\nunit");
+    let svc_builder = ServiceConfiguration::builder()
+        .exec_start(vec![
+            "/usr/bin/printf",
+            "%s\n",
+            "two words",
+            "say \"hi\"",
+            "C:\\temp",
+            "$HOME",
+            "",
+            ";",
+        ])
+        .exec_reload(vec!["/bin/kill", "-HUP", "${MAINPID}"])
+        .exec_stop(vec!["/bin/kill", "-TERM", "$MAINPID", "${1}", "${MAINPID"])
+        .env("GREETING", "it's \"50%\" $off")
+        .env("MULTILINE", "a\nb\\c");
+    let svc_unit = ServiceUnitConfiguration::builder()
+        .unit(unit_builder)
+        .service(svc_builder)
+        .install(InstallConfiguration::builder().clear_default_wanted_by())
+        .build();
+    let expected = concat!(
+        "[Unit]\n",
+        "Description=100%% hostile unit\n",
+        "\n",
+        "[Service]\n",
+        "Type=simple\n",
+        r#"Environment="GREETING=it's \"50%%\" $off""#,
+        "\n",
+        r#"Environment="MULTILINE=a\nb\\c""#,
+        "\n",
+        r#"ExecStart=/usr/bin/printf "%%s\n" "two words" "say \"hi\"" "C:\\temp" $$HOME "" \;"#,
+        "\n",
+        "ExecReload=/bin/kill -HUP ${MAINPID}\n",
+        "ExecStop=/bin/kill -TERM $$MAINPID $${1} $${MAINPID\n",
+        "Restart=no\n",
+        "RestartSec=100ms\n",
+    );
+    assert_eq!(expected, format!("{}", svc_unit));
+    // names and paths keep specifiers, literal "%" has to be escaped explicitly
+    let working_directory = escape_specifiers("/srv/100%");
+    let service = ServiceConfiguration::builder()
+        .exec_start(vec!["/usr/bin/worker"])
+        .user("%i")
+        .working_directory(&working_directory)
+        .build()
+        .to_string();
+    assert!(service.contains("User=%i\n"));
+    assert!(service.contains("WorkingDirectory=/srv/100%%\n"));
+    // a secret must neither inject directives nor expand specifiers
+    let exec = ExecConfiguration::builder().set_credential("token", "100%\n[Install]\\x");
+    let service = ServiceConfiguration::builder()
+        .exec_start(vec!["/usr/bin/worker"])
+        .exec(exec)
+        .build()
+        .to_string();
+    assert!(
+        service.contains("SetCredential=token:100%%\\n[Install]\\\\x\n"),
+        "{}",
+        service
+    );
+    assert!(!service.contains("\n[Install]"));
+}
+
+#[test]
+fn test_drop_in_template() {
+    let drop_in = DropInConfiguration::builder()
+        .section(
+            UnitConfiguration::builder()
+                .reset("After")
+                .after("network-online.target"),
+        )
+        .section(
+            ServiceConfiguration::builder()
+                .exec_start(vec!["/usr/bin/vendord", "--verbose"])
+                .restart_policy(RestartPolicy::Always)
+                .reset("Environment")
+                .env("DEBUG", "1")
+                .resource_control(
+                    ResourceControlConfiguration::builder().memory_max(ByteSize::mebibytes(512)),
+                ),
+        )
+        .section(InstallConfiguration::builder())
+        .build();
+    let expected = r#"[Unit]
+After=
+After=network-online.target
+
+[Service]
+ExecStart=
+Environment=
+Environment="DEBUG=1"
+ExecStart=/usr/bin/vendord --verbose
+Restart=always
+MemoryMax=512M
+"#;
+    assert_eq!(expected, drop_in.to_string());
+    let drop_in = DropInConfiguration::builder()
+        .section(MountConfiguration::builder().option("noatime"))
+        .section(
+            InstallConfiguration::builder()
+                .reset("WantedBy")
+                .wanted_by("remote-fs.target"),
+        )
+        .build();
+    assert_eq!(
+        "[Mount]\nOptions=noatime\n\n[Install]\nWantedBy=\nWantedBy=remote-fs.target\n",
+        drop_in.to_string()
+    );
+    // a full section keeps the plain defaults
+    let service = ServiceConfiguration::builder()
+        .exec_start(vec!["/usr/bin/vendord"])
+        .build();
+    assert!(matches!(service.ty, ServiceType::Simple));
+    assert!(matches!(service.restart_policy, RestartPolicy::No));
+    assert_eq!("100ms", service.restart_sec);
+}
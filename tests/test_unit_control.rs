@@ -0,0 +1,57 @@
+use systemd_client::{manager, unit, UnitActiveStateType, UnitName, UnitSubStateType};
+
+// sub states are sent over D-Bus as strings, so they must survive a round trip
+#[test]
+fn test_sub_state_round_trip() {
+    for state in [
+        "deactivating-sigterm",
+        "deactivating-sigkill",
+        "final-sigterm",
+        "stop-sigabrt",
+        "unmounting-sigkill",
+        "start-chown",
+    ] {
+        let sub_state: UnitSubStateType = state.to_owned().into();
+        assert_ne!(UnitSubStateType::Other(state.to_owned()), sub_state);
+        assert_eq!(state, sub_state.to_string());
+    }
+}
+
+// dbus.service is always running on a system bus, starting it again and sending signal 0 are no-ops
+#[test]
+fn test_blocking() {
+    let unit_name: UnitName = "dbus.service".parse().expect("parse unit name failed");
+    let client = manager::build_blocking_proxy().expect("build blocking client failed");
+    let unit_path = client.get_unit(&unit_name).expect("get unit failed");
+    let client = unit::build_blocking_proxy(unit_path).expect("build blocking unit client failed");
+    let job_path = client.start("replace").expect("start unit failed");
+    println!("{}", job_path.as_str());
+    client.kill("main", 0).expect("kill unit failed");
+    client.reset_failed().expect("reset failed unit failed");
+    let unit_props = client.get_properties().expect("get properties failed");
+    assert_eq!(UnitActiveStateType::Active, unit_props.active_state);
+}
+
+#[tokio::test]
+async fn test_nonblock() {
+    let unit_name: UnitName = "dbus.service".parse().expect("parse unit name failed");
+    let client = manager::build_nonblock_proxy()
+        .await
+        .expect("build nonblock client failed");
+    let unit_path = client.get_unit(&unit_name).await.expect("get unit failed");
+    let client = unit::build_nonblock_proxy(unit_path)
+        .await
+        .expect("build nonblock unit client failed");
+    let job_path = client.start("replace").await.expect("start unit failed");
+    println!("{}", job_path.as_str());
+    client.kill("main", 0).await.expect("kill unit failed");
+    client
+        .reset_failed()
+        .await
+        .expect("reset failed unit failed");
+    let unit_props = client
+        .get_properties()
+        .await
+        .expect("get properties failed");
+    assert_eq!(UnitActiveStateType::Active, unit_props.active_state);
+}
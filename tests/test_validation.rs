@@ -0,0 +1,194 @@
+mod common;
+
+use common::{read_resource, TempDir};
+use systemd_client::{
+    validate_unit_document, validate_unit_file, Diagnostic, DiagnosticLevel, LookupPaths,
+    ServiceConfiguration, ServiceUnitConfiguration, UnitConfiguration, UnitName,
+};
+
+#[test]
+fn test_validate_valid_units() {
+    for name in [
+        "test.service",
+        "test_oneshot.service",
+        "vendor.service",
+        "test.socket",
+        "test.timer",
+        "test.path",
+    ] {
+        let unit_name: UnitName = name.parse().expect("invalid unit name");
+        let diagnostics = validate_unit_file(&read_resource(name), &unit_name)
+            .unwrap_or_else(|_| panic!("validate '{}' failed", name));
+        assert!(diagnostics.is_empty(), "{}: {:?}", name, diagnostics);
+    }
+    let svc_unit = ServiceUnitConfiguration::builder()
+        .unit(UnitConfiguration::builder().description("built service"))
+        .service(ServiceConfiguration::builder().exec_start(vec!["/bin/sleep", "3"]))
+        .build();
+    let unit_name: UnitName = "built.service".parse().expect("invalid unit name");
+    let diagnostics =
+        validate_unit_file(&svc_unit.to_string(), &unit_name).expect("validate unit file failed");
+    assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+}
+
+#[test]
+fn test_validate_invalid_service() {
+    let unit_name: UnitName = "invalid.service".parse().expect("invalid unit name");
+    let diagnostics = validate_unit_file(&read_resource("invalid.service"), &unit_name)
+        .expect("validate 'invalid.service' failed");
+    let found: Vec<(DiagnosticLevel, Option<usize>, Option<&str>)> = diagnostics
+        .iter()
+        .map(|d| (d.level, d.line, d.key.as_deref()))
+        .collect();
+    assert_eq!(
+        vec![
+            (DiagnosticLevel::Error, Some(3), Some("RequiresMountsFor")),
+            (DiagnosticLevel::Error, Some(6), None),
+            (DiagnosticLevel::Error, Some(8), Some("Restart")),
+            (DiagnosticLevel::Error, Some(9), Some("TimeoutStartSec")),
+            (DiagnosticLevel::Error, Some(10), Some("MemoryMax")),
+            (DiagnosticLevel::Error, Some(11), Some("WorkingDirectory")),
+            (DiagnosticLevel::Warning, Some(12), Some("Frobnicate")),
+            (DiagnosticLevel::Warning, Some(14), None),
+        ],
+        found
+    );
+    assert_eq!(
+        "error: 9: [Service] TimeoutStartSec= \"5 minutes later\" is not a valid time span",
+        diagnostics[3].to_string()
+    );
+    // syntax errors are not diagnostics
+    assert!(validate_unit_file("Description=no section", &unit_name).is_err());
+}
+
+#[test]
+fn test_validate_other_unit_types() {
+    let cases = [
+        ("a.socket", "[Socket]\nAccept=yes\nService=b.service\n", 2),
+        ("a.timer", "[Timer]\nPersistent=yes\n", 1),
+        ("a.path", "[Path]\nUnit=a.service\n", 1),
+        (
+            "srv-data.mount",
+            "[Mount]\nWhat=/dev/sdb1\nWhere=/srv/other\n",
+            1,
+        ),
+        ("a.service", "[Service]\nType=dbus\nExecStart=/bin/a\n", 1),
+        (
+            "a.service",
+            "[Service]\nExecStart=/bin/a\nExecStart=/bin/b\n",
+            1,
+        ),
+        (
+            "a.service",
+            "[Service]\nExecStart=/bin/a\nExecStart=\nExecStart=/bin/b\n",
+            0,
+        ),
+        (
+            "a.service",
+            "[Service]\nRestartSec=1h 30min\nIOWriteBandwidthMax=/dev/sda 5M\n",
+            1,
+        ),
+        // without Type= a service is oneshot unless ExecStart= or BusName= is set
+        ("a.service", "[Service]\nExecStop=/bin/x\n", 0),
+        (
+            "a.service",
+            "[Service]\nExecStop=/bin/x\nRestart=always\n",
+            1,
+        ),
+        (
+            "a.service",
+            "[Service]\nBusName=org.example.A\nExecStart=/bin/a\nExecStart=/bin/b\n",
+            1,
+        ),
+        // systemd refuses a service without [Service] section
+        ("a.service", "[Unit]\nDescription=a\n", 1),
+        ("a.socket", "[Unit]\nDescription=a\n", 1),
+        (
+            "a.service",
+            "[Service]\nExecStart=/bin/a\nTimerSlackNSec=50ns\nX-RetrySec=later\n",
+            0,
+        ),
+    ];
+    for (name, content, errors) in cases {
+        let unit_name: UnitName = name.parse().expect("invalid unit name");
+        let diagnostics = validate_unit_file(content, &unit_name)
+            .unwrap_or_else(|_| panic!("validate {:?} failed", content));
+        let count = diagnostics
+            .iter()
+            .filter(|d| d.level == DiagnosticLevel::Error)
+            .count();
+        assert_eq!(errors, count, "{}: {:?}", name, diagnostics);
+    }
+    // resource control settings apply to slices, except for the parent slice
+    let unit_name: UnitName = "a.slice".parse().expect("invalid unit name");
+    let diagnostics = validate_unit_file("[Slice]\nSlice=b.slice\nCPUWeight=50\n", &unit_name)
+        .expect("validate 'a.slice' failed");
+    let found: Vec<(DiagnosticLevel, Option<&str>)> = diagnostics
+        .iter()
+        .map(|d| (d.level, d.key.as_deref()))
+        .collect();
+    assert_eq!(vec![(DiagnosticLevel::Warning, Some("Slice"))], found);
+}
+
+#[test]
+fn test_validate_effective_configuration() {
+    let root = TempDir::new("validation");
+    let vendor = root.path().join("usr");
+    let admin = root.path().join("etc");
+    std::fs::create_dir_all(admin.join("foo.service.d")).expect("create directory failed");
+    std::fs::create_dir_all(vendor.as_path()).expect("create directory failed");
+    std::fs::write(
+        vendor.join("foo.service"),
+        "[Unit]\nDescription=foo\n\n[Service]\nExecStart=/bin/foo\nTimeoutStartSec=soon\n",
+    )
+    .expect("write 'foo.service' failed");
+    std::fs::write(
+        admin.join("foo.service.d/override.conf"),
+        "[Service]\nExecStart=/bin/bar\n",
+    )
+    .expect("write 'override.conf' failed");
+    let lookup = LookupPaths::new(vec![admin.clone(), vendor.clone()]);
+    let unit_name: UnitName = "foo.service".parse().expect("invalid unit name");
+    let configuration = lookup
+        .load_effective_configuration(&unit_name)
+        .expect("load 'foo.service' failed");
+    let diagnostics = validate_unit_document(&configuration.document, &unit_name);
+    let found: Vec<(Option<usize>, Option<&str>)> = diagnostics
+        .iter()
+        .map(|d| (d.line, d.key.as_deref()))
+        .collect();
+    // sorted by file, the drop-in adds a second ExecStart= instead of replacing the first one
+    assert_eq!(
+        vec![
+            (Some(6), Some("TimeoutStartSec")),
+            (Some(2), Some("ExecStart"))
+        ],
+        found
+    );
+    assert_eq!(Some(vendor.join("foo.service")), diagnostics[0].origin);
+    assert_eq!(
+        Some(admin.join("foo.service.d/override.conf")),
+        diagnostics[1].origin
+    );
+}
+
+#[test]
+fn test_diagnostic_display() {
+    let mut diagnostic = Diagnostic {
+        level: DiagnosticLevel::Error,
+        line: None,
+        origin: Some("/etc/systemd/system/a.service".into()),
+        section: "Service".to_owned(),
+        key: None,
+        message: "service has no ExecStart=, ExecStop= or SuccessAction=".to_owned(),
+    };
+    assert_eq!(
+        "error: /etc/systemd/system/a.service: [Service] service has no ExecStart=, ExecStop= or SuccessAction=",
+        diagnostic.to_string()
+    );
+    diagnostic.line = Some(4);
+    assert_eq!(
+        "error: /etc/systemd/system/a.service:4: [Service] service has no ExecStart=, ExecStop= or SuccessAction=",
+        diagnostic.to_string()
+    );
+}